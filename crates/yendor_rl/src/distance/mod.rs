@@ -14,6 +14,7 @@ mod pythagoras;
 pub use pythagoras::*;
 
 /// Enumeration of available 2D/3D Distance algorithms
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Distance {
     /// Use the Pythagoras algorithm for determining distance - sqrt(A^2 + B^2)
     Pythagoras,
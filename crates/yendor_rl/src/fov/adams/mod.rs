@@ -9,11 +9,27 @@ impl FovAlgorithm for AdamsFov {
         origin: IVec2,
         range: u32,
         provider: &mut impl FovProvider<T>,
-        mut pass_through_data: T,
+        pass_through_data: T,
     ) -> HashSet<IVec2> {
         let mut visible_points = HashSet::with_capacity(((range * 2) * (range * 2)) as usize);
+        Self::compute_fov_into(
+            origin,
+            range,
+            provider,
+            &mut visible_points,
+            pass_through_data,
+        );
+        visible_points
+    }
 
-        visible_points.insert(origin);
+    fn compute_fov_into<T>(
+        origin: IVec2,
+        range: u32,
+        provider: &mut impl FovProvider<T>,
+        receiver: &mut impl FovReceiver,
+        mut pass_through_data: T,
+    ) {
+        receiver.set_visible(origin);
 
         for octant in 0..8 {
             Self::compute_octant(
@@ -25,15 +41,48 @@ impl FovAlgorithm for AdamsFov {
                 Slope { x: 1, y: 0 },
                 provider,
                 &mut pass_through_data,
-                &mut visible_points,
+                receiver,
             )
         }
-
-        visible_points
     }
 }
 
 impl AdamsFov {
+    /// Computes the field of view within a cone of vision.
+    pub fn compute_cone<T>(
+        origin: IVec2,
+        range: u32,
+        provider: &mut impl FovProvider<T>,
+        cone: VisionCone,
+        mut pass_through_data: T,
+    ) -> HashSet<IVec2> {
+        let mut visible_points = HashSet::with_capacity(((range * 2) * (range * 2)) as usize);
+
+        visible_points.insert(origin);
+
+        for octant in 0..8 {
+            let forward = Self::transform(1, 0, octant, IVec2::ZERO);
+            let side = Self::transform(0, 1, octant, IVec2::ZERO);
+            for (bottom, top) in
+                cone.clip(forward, side, Slope { x: 1, y: 0 }, Slope { x: 1, y: 1 })
+            {
+                Self::compute_octant(
+                    octant,
+                    origin,
+                    range as i32,
+                    1,
+                    top,
+                    bottom,
+                    provider,
+                    &mut pass_through_data,
+                    &mut visible_points,
+                )
+            }
+        }
+
+        visible_points
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn compute_octant<T>(
         octant: i32,
@@ -44,7 +93,7 @@ impl AdamsFov {
         mut bottom: Slope,
         provider: &mut impl FovProvider<T>,
         pass_through_data: &mut T,
-        visible_points: &mut HashSet<IVec2>,
+        receiver: &mut impl FovReceiver,
     ) {
         for x in x..=range {
             let y_coords = Self::compute_y(
@@ -71,7 +120,7 @@ impl AdamsFov {
                 &mut bottom,
                 provider,
                 pass_through_data,
-                visible_points,
+                receiver,
             ) {
                 break;
             }
@@ -153,7 +202,7 @@ impl AdamsFov {
         bottom: &mut Slope,
         provider: &mut impl FovProvider<T>,
         pass_through_data: &mut T,
-        visible_points: &mut HashSet<IVec2>,
+        receiver: &mut impl FovReceiver,
     ) -> bool {
         let mut was_opaque = -1;
 
@@ -170,7 +219,7 @@ impl AdamsFov {
                 );
 
                 if is_visible {
-                    Self::set_visible(x, y, octant, origin, visible_points);
+                    Self::set_visible(x, y, octant, origin, receiver);
                 }
 
                 if x != range {
@@ -202,7 +251,7 @@ impl AdamsFov {
                                         Slope { y: ny, x: nx },
                                         provider,
                                         pass_through_data,
-                                        visible_points,
+                                        receiver,
                                     );
                                 }
                             } else if y == bottom_y {
@@ -250,15 +299,9 @@ impl AdamsFov {
         provider.is_opaque(xy, pass_through_data)
     }
 
-    fn set_visible(
-        x: i32,
-        y: i32,
-        octant: i32,
-        origin: IVec2,
-        visible_points: &mut HashSet<IVec2>,
-    ) {
+    fn set_visible(x: i32, y: i32, octant: i32, origin: IVec2, receiver: &mut impl FovReceiver) {
         let xy = Self::transform(x, y, octant, origin);
-        visible_points.insert(xy);
+        receiver.set_visible(xy);
     }
 
     const fn transform(x: i32, y: i32, octant: i32, origin: IVec2) -> IVec2 {
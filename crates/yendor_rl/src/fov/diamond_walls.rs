@@ -0,0 +1,122 @@
+use crate::prelude::*;
+
+/// Computes the field of view treating opaque tiles as diamonds.
+/// [Diamond Walls](http://www.adammil.net/blog/v125_Roguelike_Vision_Algorithms.html#diamond)
+///
+/// Light travels from the center of the origin to any part of a tile, while walls only block
+/// the diamond inscribed in their tile. This lets light through diagonal gaps between walls and
+/// around the corners of pillars.
+pub struct DiamondWallsFov;
+
+impl FovAlgorithm for DiamondWallsFov {
+    fn compute_fov<T>(
+        origin: IVec2,
+        range: u32,
+        provider: &mut impl FovProvider<T>,
+        mut pass_through_data: T,
+    ) -> HashSet<IVec2> {
+        let mut visible_points = HashSet::with_capacity(((range * 2) * (range * 2)) as usize);
+
+        visible_points.insert(origin);
+
+        for octant in 0..8 {
+            Self::compute_octant(
+                Octant(octant),
+                origin,
+                range as i32,
+                provider,
+                &mut pass_through_data,
+                &mut visible_points,
+            );
+        }
+
+        visible_points
+    }
+}
+
+impl DiamondWallsFov {
+    // slopes are measured on a grid of half tiles, so the corners of tiles and the vertices of
+    // diamonds land on whole numbers
+    fn compute_octant<T>(
+        octant: Octant,
+        origin: IVec2,
+        range: i32,
+        provider: &mut impl FovProvider<T>,
+        pass_through_data: &mut T,
+        visible_points: &mut HashSet<IVec2>,
+    ) {
+        let mut lit = vec![(Slope::new(0, 1), Slope::new(1, 1))];
+        for x in 1..=range {
+            let mut blockers = Vec::new();
+            for y in 0..=x {
+                let position = origin + octant.from_offset((x, y));
+                let tile = (
+                    Slope::new(2 * y - 1, 2 * x + 1),
+                    Slope::new(2 * y + 1, 2 * x - 1),
+                );
+
+                if x * x + y * y <= range * range
+                    && lit.iter().any(|arc| Self::overlaps(*arc, tile))
+                {
+                    visible_points.insert(position);
+                }
+
+                if provider.is_opaque(position, pass_through_data) {
+                    blockers.push(Self::diamond(x, y));
+                }
+            }
+
+            // walls only cast their shadow onto the following columns
+            for blocker in blockers {
+                lit = lit
+                    .into_iter()
+                    .flat_map(|arc| Self::subtract(arc, blocker))
+                    .collect();
+            }
+
+            if lit.is_empty() {
+                break;
+            }
+        }
+    }
+
+    // the arc between the lowest and highest vertex of the diamond in the tile
+    const fn diamond(x: i32, y: i32) -> (Slope, Slope) {
+        let left = Slope::new(2 * y, 2 * x - 1);
+        let right = Slope::new(2 * y, 2 * x + 1);
+        let bottom = Slope::new(2 * y - 1, 2 * x);
+        let top = Slope::new(2 * y + 1, 2 * x);
+
+        let low = if right.greater(bottom.y, bottom.x) {
+            bottom
+        } else {
+            right
+        };
+        let high = if left.greater(top.y, top.x) {
+            left
+        } else {
+            top
+        };
+        (low, high)
+    }
+
+    const fn overlaps((a_low, a_high): (Slope, Slope), (b_low, b_high): (Slope, Slope)) -> bool {
+        a_high.greater(b_low.y, b_low.x) && b_high.greater(a_low.y, a_low.x)
+    }
+
+    fn subtract(arc: (Slope, Slope), blocker: (Slope, Slope)) -> Vec<(Slope, Slope)> {
+        if !Self::overlaps(arc, blocker) {
+            return vec![arc];
+        }
+
+        let (low, high) = arc;
+        let mut remaining = Vec::with_capacity(2);
+        if blocker.0.greater(low.y, low.x) {
+            remaining.push((low, blocker.0));
+        }
+        if high.greater(blocker.1.y, blocker.1.x) {
+            remaining.push((blocker.1, high));
+        }
+        remaining
+    }
+}
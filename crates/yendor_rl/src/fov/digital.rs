@@ -0,0 +1,178 @@
+use crate::prelude::*;
+
+/// Computes the field of view using digital lines.
+/// [Digital Fov](http://www.adammil.net/blog/v125_Roguelike_Vision_Algorithms.html#digital)
+///
+/// A tile is visible if any digital line (a rasterized line, like Bresenham's) connects it to
+/// the origin without passing through an opaque tile. Every reversed digital line is a digital
+/// line as well, which makes the field of view symmetric.
+pub struct DigitalFov;
+
+impl FovAlgorithm for DigitalFov {
+    fn compute_fov<T>(
+        origin: IVec2,
+        range: u32,
+        provider: &mut impl FovProvider<T>,
+        mut pass_through_data: T,
+    ) -> HashSet<IVec2> {
+        let mut visible_points = HashSet::with_capacity(((range * 2) * (range * 2)) as usize);
+
+        visible_points.insert(origin);
+
+        let range = range as i32;
+        for octant in (0..8).map(Octant) {
+            for x in 1..=range {
+                for y in 0..=x {
+                    if x * x + y * y > range * range {
+                        break;
+                    }
+
+                    let position = origin + octant.from_offset((x, y));
+                    if !visible_points.contains(&position)
+                        && Self::has_digital_line(
+                            &octant,
+                            origin,
+                            x,
+                            y,
+                            provider,
+                            &mut pass_through_data,
+                        )
+                    {
+                        visible_points.insert(position);
+                    }
+                }
+            }
+        }
+
+        visible_points
+    }
+}
+
+// half the width of the gap kept below the top of a cell, so `floor` never rounds up
+const EPSILON: f64 = 1e-9;
+
+// the points `(slope, intercept)` where `slope * x + intercept + offset >= 0`, as `[x, 1, offset]`
+type HalfPlane = [f64; 3];
+
+impl DigitalFov {
+    // searches the digital lines `y = floor(slope * x + intercept)` with `0 <= slope <= 1` and
+    // `0 <= intercept < 1` from (0, 0) to (x, y) in the octant. The lines still possible are
+    // a convex polygon of `(slope, intercept)`, split by the rows each column may pass through,
+    // and only the tiles in between the endpoints need to be clear.
+    fn has_digital_line<T>(
+        octant: &Octant,
+        origin: IVec2,
+        x: i32,
+        y: i32,
+        provider: &mut impl FovProvider<T>,
+        pass_through_data: &mut T,
+    ) -> bool {
+        let lines = vec![
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (1.0, 1.0 - EPSILON),
+            (0.0, 1.0 - EPSILON),
+        ];
+        let lines = Self::clip(lines, Self::row(x, y));
+        Self::search(octant, origin, 1, x, lines, provider, pass_through_data)
+    }
+
+    fn search<T>(
+        octant: &Octant,
+        origin: IVec2,
+        column: i32,
+        end: i32,
+        lines: Vec<(f64, f64)>,
+        provider: &mut impl FovProvider<T>,
+        pass_through_data: &mut T,
+    ) -> bool {
+        if lines.is_empty() {
+            return false;
+        }
+        if column >= end {
+            return true;
+        }
+
+        let heights = lines
+            .iter()
+            .map(|(slope, intercept)| slope * column as f64 + intercept);
+        let low = heights.clone().fold(f64::MAX, f64::min).floor() as i32;
+        let high = heights.fold(f64::MIN, f64::max).floor() as i32;
+        (low..=high).any(|row| {
+            let position = origin + octant.from_offset((column, row));
+            !provider.is_opaque(position, pass_through_data)
+                && Self::search(
+                    octant,
+                    origin,
+                    column + 1,
+                    end,
+                    Self::clip(lines.clone(), Self::row(column, row)),
+                    provider,
+                    pass_through_data,
+                )
+        })
+    }
+
+    // the lines passing through the tile (column, row)
+    fn row(column: i32, row: i32) -> [HalfPlane; 2] {
+        let column = column as f64;
+        [
+            [column, 1.0, -(row as f64)],
+            [-column, -1.0, row as f64 + 1.0 - EPSILON],
+        ]
+    }
+
+    // clips the convex polygon to the half planes
+    fn clip(mut polygon: Vec<(f64, f64)>, half_planes: [HalfPlane; 2]) -> Vec<(f64, f64)> {
+        for [a, b, c] in half_planes {
+            let side = |(x, y): (f64, f64)| a.mul_add(x, b.mul_add(y, c));
+            let mut clipped = Vec::with_capacity(polygon.len() + 1);
+            for (index, point) in polygon.iter().enumerate() {
+                let next = polygon[(index + 1) % polygon.len()];
+                let (from, to) = (side(*point), side(next));
+                if from >= 0.0 {
+                    clipped.push(*point);
+                }
+                if (from >= 0.0) != (to >= 0.0) {
+                    let t = from / (from - to);
+                    clipped.push((
+                        (next.0 - point.0).mul_add(t, point.0),
+                        (next.1 - point.1).mul_add(t, point.1),
+                    ));
+                }
+            }
+            polygon = clipped;
+            if polygon.is_empty() {
+                break;
+            }
+        }
+        polygon
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn shallow_line() {
+        // (6, -2) is reached through floor(0.3 * x + 0.45), which no line of slope 2/6 fits
+        let mut walls = Grid::new_copy(UVec2::new(9, 3), true);
+        for position in [
+            (2, 2),
+            (3, 2),
+            (4, 1),
+            (5, 1),
+            (6, 1),
+            (7, 1),
+            (8, 0),
+            (8, 2),
+        ] {
+            walls[IVec2::new(position.0, position.1)] = false;
+        }
+        let mut provider = GridFovProvider::new(&walls, |wall: &bool| *wall);
+        let visible = DigitalFov::compute_fov(IVec2::new(2, 2), 10, &mut provider, ());
+        assert!(visible.contains(&IVec2::new(8, 0)));
+        assert!(!visible.contains(&IVec2::new(8, 2)));
+    }
+}
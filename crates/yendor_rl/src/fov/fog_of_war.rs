@@ -0,0 +1,212 @@
+use crate::prelude::*;
+
+/// What an actor knows about a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum TileVisibility {
+    /// The cell has never been seen.
+    Unknown,
+    /// The cell has been seen before, but is out of sight now.
+    Remembered,
+    /// The cell is in sight.
+    Visible,
+}
+
+/// The cells an actor can see and the cells it remembers, along with what they looked like when
+/// they were last seen.
+///
+/// Every turn, clear the visible cells, compute the field of view into the fog and snapshot the
+/// visible cells of the map:
+///
+/// ```ignore
+/// fog.clear_visible();
+/// Fov::Shadowcast.compute_into(position, 8_u32, &mut provider, &mut fog, ());
+/// fog.remember(&map);
+/// ```
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct FogOfWar<T> {
+    visible: BitGrid,
+    explored: BitGrid,
+    remembered: Grid<Option<T>>,
+}
+
+impl<T> FogOfWar<T> {
+    /// Creates a new fog where every cell is unknown.
+    pub fn new(dimensions: UVec2) -> Self {
+        Self {
+            visible: BitGrid::new_default(dimensions),
+            explored: BitGrid::new_default(dimensions),
+            remembered: Grid::new_fn(dimensions, |_| None),
+        }
+    }
+
+    /// Returns the dimensions of the fog.
+    #[inline]
+    pub fn dimensions(&self) -> UVec2 {
+        self.visible.dimensions()
+    }
+
+    /// Returns the cells in sight.
+    #[inline]
+    pub const fn visible(&self) -> &BitGrid {
+        &self.visible
+    }
+
+    /// Returns the cells that have ever been seen.
+    #[inline]
+    pub const fn explored(&self) -> &BitGrid {
+        &self.explored
+    }
+
+    /// Returns what the actor knows about the position, positions outside the fog are unknown.
+    pub fn visibility(&self, position: IVec2) -> TileVisibility {
+        if self.visible.get_visible(position) {
+            TileVisibility::Visible
+        } else if self.explored.get_visible(position) {
+            TileVisibility::Remembered
+        } else {
+            TileVisibility::Unknown
+        }
+    }
+
+    /// Returns true if the position is in sight.
+    #[inline]
+    pub fn is_visible(&self, position: IVec2) -> bool {
+        self.visible.get_visible(position)
+    }
+
+    /// Returns true if the position has ever been seen.
+    #[inline]
+    pub fn is_explored(&self, position: IVec2) -> bool {
+        self.explored.get_visible(position)
+    }
+
+    /// Returns the cell as it looked when it was last seen by [`FogOfWar::remember`].
+    pub fn remembered(&self, position: IVec2) -> Option<&T> {
+        if position.is_valid(self.dimensions()) {
+            self.remembered[position].as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Snapshots every visible cell of the map, to be recalled once it is out of sight.
+    pub fn remember(&mut self, grid: &Grid<T>)
+    where
+        T: Clone,
+    {
+        for position in self.visible.point_iter() {
+            if self.visible[position] {
+                if let Some(cell) = grid.get(position.as_uvec2()) {
+                    self.remembered[position] = Some(cell.clone());
+                }
+            }
+        }
+    }
+
+    /// Replaces the visible cells with the field of view and snapshots them.
+    pub fn update(&mut self, visible: &HashSet<IVec2>, grid: &Grid<T>)
+    where
+        T: Clone,
+    {
+        self.clear_visible();
+        for position in visible {
+            self.set_visible(*position);
+        }
+        self.remember(grid);
+    }
+
+    /// Forgets everything about the position, making it unknown again.
+    pub fn forget(&mut self, position: IVec2) {
+        if position.is_valid(self.dimensions()) {
+            self.visible.set_unchecked(position.as_uvec2(), false);
+            self.explored.set_unchecked(position.as_uvec2(), false);
+            self.remembered[position] = None;
+        }
+    }
+
+    /// Forgets everything, making every cell unknown again.
+    pub fn forget_all(&mut self) {
+        self.visible.clear_visible();
+        self.explored.clear_visible();
+        self.remembered.iter_mut().for_each(|cell| *cell = None);
+    }
+}
+
+/// Visible positions are explored as well, positions outside the fog are ignored.
+impl<T> FovReceiver for FogOfWar<T> {
+    #[inline]
+    fn set_visible(&mut self, position: IVec2) {
+        self.visible.set_visible(position);
+        self.explored.set_visible(position);
+    }
+
+    #[inline]
+    fn get_visible(&self, position: IVec2) -> bool {
+        self.visible.get_visible(position)
+    }
+
+    /// Clears the visible cells, the explored cells are kept.
+    #[inline]
+    fn clear_visible(&mut self) {
+        self.visible.clear_visible();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn remember() {
+        let mut map = Grid::new_copy(UVec2::new(12, 3), '.');
+        map[IVec2::new(6, 1)] = '#';
+        let mut provider = GridFovProvider::new(&map, |tile: &char| *tile == '#');
+
+        let mut fog = FogOfWar::new(map.dimensions());
+        let visible = Fov::Shadowcast.compute(IVec2::new(3, 1), 4_u32, &mut provider, ());
+        fog.update(&visible, &map);
+        assert_eq!(fog.visibility(IVec2::new(5, 1)), TileVisibility::Visible);
+        assert_eq!(fog.visibility(IVec2::new(9, 1)), TileVisibility::Unknown);
+        assert_eq!(fog.remembered(IVec2::new(6, 1)), Some(&'#'));
+
+        // the wall is knocked down out of sight
+        let mut knocked_down = map.clone();
+        knocked_down[IVec2::new(6, 1)] = '.';
+        fog.clear_visible();
+        Fov::Shadowcast.compute_into(IVec2::new(0, 1), 1_u32, &mut provider, &mut fog, ());
+        fog.remember(&knocked_down);
+        assert_eq!(fog.visibility(IVec2::new(1, 1)), TileVisibility::Visible);
+        assert_eq!(fog.visibility(IVec2::new(6, 1)), TileVisibility::Remembered);
+        assert_eq!(fog.remembered(IVec2::new(6, 1)), Some(&'#'));
+        assert_eq!(fog.remembered(IVec2::new(9, 1)), None);
+
+        fog.forget(IVec2::new(6, 1));
+        assert_eq!(fog.visibility(IVec2::new(6, 1)), TileVisibility::Unknown);
+        assert_eq!(fog.remembered(IVec2::new(6, 1)), None);
+    }
+
+    #[test]
+    #[cfg(feature = "serialize")]
+    fn round_trip() {
+        let mut map = Grid::new_copy(UVec2::new(5, 1), '.');
+        map[IVec2::new(2, 0)] = '#';
+        let mut provider = GridFovProvider::new(&map, |tile: &char| *tile == '#');
+
+        let mut fog = FogOfWar::new(map.dimensions());
+        let visible = Fov::Shadowcast.compute(IVec2::new(0, 0), 4_u32, &mut provider, ());
+        fog.update(&visible, &map);
+        fog.clear_visible();
+
+        let serialized = ron::to_string(&fog).unwrap();
+        let deserialized: FogOfWar<char> = ron::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, fog);
+        assert_eq!(
+            deserialized.visibility(IVec2::new(2, 0)),
+            TileVisibility::Remembered
+        );
+        assert_eq!(deserialized.remembered(IVec2::new(2, 0)), Some(&'#'));
+        assert_eq!(deserialized.remembered(IVec2::new(4, 0)), None);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::prelude::*;
+
+// a single layer of a `FovProvider3d`
+struct LayerProvider<'a, T, P> {
+    provider: &'a mut P,
+    pass_through_data: &'a mut T,
+    layer: i32,
+}
+
+impl<'a, T, P: FovProvider3d<T>> FovProvider<()> for LayerProvider<'a, T, P> {
+    fn is_opaque(&mut self, position: IVec2, _pass_through_data: &mut ()) -> bool {
+        self.provider
+            .is_opaque(position.extend(self.layer), self.pass_through_data)
+    }
+}
+
+impl Fov {
+    /// Computes the field of view across the layers of the provider.
+    ///
+    /// Sight passes between layers where [`FovProvider3d::is_open_above`] allows it. The cells
+    /// straight above or below a visible cell can be seen through the opening between them, and
+    /// on every layer reached straight up or down from the origin, such as the landings of a
+    /// stairwell, the view spreads out as if the origin stood there.
+    pub fn compute_3d<FovRange: Into<u32>, T>(
+        &self,
+        origin: IVec3,
+        range: FovRange,
+        provider: &mut impl FovProvider3d<T>,
+        mut pass_through_data: T,
+    ) -> HashSet<IVec3> {
+        let range = range.into() as i32;
+        let up = Direction::UP.coord3d();
+        let down = Direction::DOWN.coord3d();
+
+        // the layers the origin can see straight into
+        let mut centers = vec![origin];
+        for step in [up, down] {
+            let mut position = origin;
+            while (position - origin).z.abs() < range
+                && Self::is_open(position, step, provider, &mut pass_through_data)
+            {
+                position += step;
+                centers.push(position);
+            }
+        }
+
+        let mut visible_points = HashSet::new();
+        for center in centers {
+            let remaining = range - (center - origin).z.abs();
+            let mut layer = LayerProvider {
+                provider,
+                pass_through_data: &mut pass_through_data,
+                layer: center.z,
+            };
+            visible_points.extend(
+                self.compute(center.truncate(), remaining as u32, &mut layer, ())
+                    .into_iter()
+                    .map(|position| position.extend(center.z)),
+            );
+        }
+
+        // look through the openings above and below every visible cell
+        for position in visible_points.clone() {
+            for step in [up, down] {
+                let mut position = position;
+                while Self::is_open(position, step, provider, &mut pass_through_data) {
+                    position += step;
+                    let offset = position - origin;
+                    if offset.dot(offset) > range * range {
+                        break;
+                    }
+                    visible_points.insert(position);
+                }
+            }
+        }
+
+        visible_points
+    }
+
+    fn is_open<T>(
+        position: IVec3,
+        step: IVec3,
+        provider: &mut impl FovProvider3d<T>,
+        pass_through_data: &mut T,
+    ) -> bool {
+        if step.z > 0 {
+            provider.is_open_above(position, pass_through_data)
+        } else {
+            provider.is_open_above(position + step, pass_through_data)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    // `#` walls, `.` floors, ` ` open air above the layer below and `<` stairs to the layer above
+    struct Fortress(Grid3d<char, 3>);
+    impl Fortress {
+        fn new() -> Self {
+            let mut grid = Grid3d::new_copy(UVec2::new(9, 5), '.');
+            grid.set(1_usize, UVec2::new(1, 2), '<');
+            grid.set(1_usize, UVec2::new(6, 2), ' ');
+            for y in 0..5 {
+                grid.set(2_usize, UVec2::new(4, y), '#');
+            }
+            Self(grid)
+        }
+
+        fn tile(&self, position: IVec3) -> Option<char> {
+            if position.z < 0 || !position.truncate().is_valid(self.0.dimensions()) {
+                return None;
+            }
+            self.0
+                .get(position.z as usize, position.truncate().as_uvec2())
+                .copied()
+        }
+    }
+
+    impl FovProvider3d<()> for Fortress {
+        fn is_opaque(&mut self, position: IVec3, _pass_through_data: &mut ()) -> bool {
+            !matches!(self.tile(position), Some('.' | ' ' | '<'))
+        }
+
+        fn is_open_above(&mut self, position: IVec3, _pass_through_data: &mut ()) -> bool {
+            self.tile(position) == Some('<') || self.tile(position + IVec3::Z) == Some(' ')
+        }
+    }
+
+    #[test]
+    fn pit() {
+        let visible =
+            Fov::Shadowcast.compute_3d(IVec3::new(2, 2, 1), 8_u32, &mut Fortress::new(), ());
+        assert!(visible.contains(&IVec3::new(6, 2, 1)));
+        assert!(visible.contains(&IVec3::new(6, 2, 0)));
+        assert!(!visible.contains(&IVec3::new(5, 2, 0)));
+        assert!(!visible.contains(&IVec3::new(2, 2, 0)));
+
+        // the stairs are seen from the side, only the landing shows
+        assert!(visible.contains(&IVec3::new(1, 2, 2)));
+        assert!(!visible.contains(&IVec3::new(2, 2, 2)));
+    }
+
+    #[test]
+    fn stairwell() {
+        let visible = Fov::Adams.compute_3d(IVec3::new(1, 2, 1), 8_u32, &mut Fortress::new(), ());
+        assert!(visible.contains(&IVec3::new(1, 2, 2)));
+        assert!(visible.contains(&IVec3::new(3, 3, 2)));
+        assert!(visible.contains(&IVec3::new(4, 2, 2)));
+        assert!(!visible.contains(&IVec3::new(6, 2, 2)));
+        assert!(!visible.contains(&IVec3::new(1, 2, 0)));
+    }
+}
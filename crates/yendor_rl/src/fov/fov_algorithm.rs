@@ -9,4 +9,18 @@ pub trait FovAlgorithm {
         provider: &mut impl FovProvider<T>,
         pass_through_data: T,
     ) -> HashSet<IVec2>;
+
+    /// Computes the field of view, marking every visible position in the receiver instead of
+    /// allocating a new set.
+    fn compute_fov_into<T>(
+        origin: IVec2,
+        range: u32,
+        provider: &mut impl FovProvider<T>,
+        receiver: &mut impl FovReceiver,
+        pass_through_data: T,
+    ) {
+        for position in Self::compute_fov(origin, range, provider, pass_through_data) {
+            receiver.set_visible(position);
+        }
+    }
 }
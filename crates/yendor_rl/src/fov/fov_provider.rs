@@ -5,3 +5,22 @@ pub trait FovProvider<T> {
     /// Returns true if the specified position is opaque.
     fn is_opaque(&mut self, position: IVec2, pass_through_data: &mut T) -> bool;
 }
+
+/// A trait for providing information about the field of view across several layers, such as the
+/// levels of a [`Grid3d`].
+pub trait FovProvider3d<T> {
+    /// Returns true if the specified position blocks sight within its layer.
+    fn is_opaque(&mut self, position: IVec3, pass_through_data: &mut T) -> bool;
+
+    /// Returns true if sight passes between the specified position and the one above it, through
+    /// an open pit, a stairwell or a missing ceiling.
+    fn is_open_above(&mut self, position: IVec3, pass_through_data: &mut T) -> bool;
+}
+
+/// A trait for providing information about a field of view through translucent tiles, such as
+/// smoke, foliage or tinted glass.
+pub trait TranslucentFovProvider<T> {
+    /// Returns how much of the view through the specified position is blocked, from `0.0` for
+    /// clear to `1.0` for opaque.
+    fn opacity(&mut self, position: IVec2, pass_through_data: &mut T) -> f32;
+}
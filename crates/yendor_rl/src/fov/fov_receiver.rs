@@ -0,0 +1,83 @@
+use crate::prelude::*;
+
+/// A trait for receiving the visible positions of a field of view.
+///
+/// Receivers are never cleared by the algorithms, so the fields of view of several origins can
+/// be gathered into one. Call [`FovReceiver::clear_visible`] before reusing a receiver for a
+/// new turn.
+pub trait FovReceiver {
+    /// Marks the position as visible.
+    fn set_visible(&mut self, position: IVec2);
+
+    /// Returns true if the position has been marked as visible.
+    fn get_visible(&self, position: IVec2) -> bool;
+
+    /// Forgets every visible position, keeping any allocated memory.
+    fn clear_visible(&mut self);
+}
+
+impl FovReceiver for HashSet<IVec2> {
+    #[inline]
+    fn set_visible(&mut self, position: IVec2) {
+        self.insert(position);
+    }
+
+    #[inline]
+    fn get_visible(&self, position: IVec2) -> bool {
+        self.contains(&position)
+    }
+
+    #[inline]
+    fn clear_visible(&mut self) {
+        self.clear();
+    }
+}
+
+/// Positions outside of the grid are ignored.
+#[cfg(feature = "bitgrid")]
+impl FovReceiver for BitGrid {
+    #[inline]
+    fn set_visible(&mut self, position: IVec2) {
+        if position.is_valid(self.dimensions) {
+            self.set_unchecked(position.as_uvec2(), true);
+        }
+    }
+
+    #[inline]
+    fn get_visible(&self, position: IVec2) -> bool {
+        position.is_valid(self.dimensions) && self[position]
+    }
+
+    #[inline]
+    fn clear_visible(&mut self) {
+        self.cells.fill(false);
+    }
+}
+
+#[cfg(all(test, feature = "bitgrid"))]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn reuse() {
+        let dimensions = UVec2::new(16, 16);
+        let mut walls = BitGrid::new_default(dimensions);
+        for y in 2..14 {
+            walls.set(UVec2::new(8, y), true);
+        }
+        let mut provider = GridFovProvider::new(&walls, |wall: &bool| *wall);
+
+        let mut visible = BitGrid::new_default(dimensions);
+        for fov in [Fov::Shadowcast, Fov::Adams] {
+            for origin in [IVec2::new(3, 8), IVec2::new(12, 1)] {
+                visible.clear_visible();
+                fov.compute_into(origin, 6_u32, &mut provider, &mut visible, ());
+
+                let expected = fov.compute(origin, 6_u32, &mut provider, ());
+                for position in visible.point_iter() {
+                    assert_eq!(visible.get_visible(position), expected.contains(&position));
+                }
+            }
+        }
+    }
+}
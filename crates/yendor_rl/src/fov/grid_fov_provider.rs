@@ -0,0 +1,64 @@
+use std::marker::PhantomData;
+
+use crate::prelude::*;
+
+/// A [`FovProvider`] reading opacity straight from a grid.
+///
+/// Positions outside of the grid are opaque.
+///
+/// ```ignore
+/// let mut provider = GridFovProvider::new(&map, |tile: &Tile| tile.blocks_sight());
+/// let visible = Fov::Shadowcast.compute(origin, 8_u32, &mut provider, ());
+/// ```
+pub struct GridFovProvider<'a, C, G, O> {
+    grid: &'a G,
+    is_opaque: O,
+    cell: PhantomData<C>,
+}
+
+impl<'a, C, G: GridLayer<C>, O: Fn(&C) -> bool> GridFovProvider<'a, C, G, O> {
+    /// Creates a new provider treating every cell matching the predicate as opaque.
+    pub const fn new(grid: &'a G, is_opaque: O) -> Self {
+        Self {
+            grid,
+            is_opaque,
+            cell: PhantomData,
+        }
+    }
+
+    /// Returns the grid this provider reads from.
+    #[inline]
+    pub const fn grid(&self) -> &'a G {
+        self.grid
+    }
+}
+
+impl<'a, T, C, G: GridLayer<C>, O: Fn(&C) -> bool> FovProvider<T> for GridFovProvider<'a, C, G, O> {
+    fn is_opaque(&mut self, position: IVec2, _pass_through_data: &mut T) -> bool {
+        !position.is_valid(self.grid.dimensions())
+            || self
+                .grid
+                .get(position.as_uvec2())
+                .map(&self.is_opaque)
+                .unwrap_or(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn wall_blocks_sight() {
+        let mut grid = Grid::new_copy(UVec2::new(8, 8), false);
+        for y in 0..8 {
+            grid[IVec2::new(4, y)] = true;
+        }
+
+        let mut provider = GridFovProvider::new(&grid, |opaque: &bool| *opaque);
+        let visible = Fov::Shadowcast.compute(IVec2::new(1, 4), 8_u32, &mut provider, ());
+        assert!(visible.contains(&IVec2::new(3, 4)));
+        assert!(visible.contains(&IVec2::new(4, 4)));
+        assert!(!visible.contains(&IVec2::new(5, 4)));
+    }
+}
@@ -4,22 +4,37 @@ use crate::prelude::*;
 mod adams;
 pub use adams::*;
 
+mod diamond_walls;
+pub use diamond_walls::*;
+mod digital;
+pub use digital::*;
+#[cfg(feature = "bitgrid")]
+mod fog_of_war;
+#[cfg(feature = "bitgrid")]
+pub use fog_of_war::*;
+mod fov_3d;
 mod fov_algorithm;
 pub use fov_algorithm::*;
 mod fov_provider;
 pub use fov_provider::*;
+mod fov_receiver;
+pub use fov_receiver::*;
+mod grid_fov_provider;
+pub use grid_fov_provider::*;
+mod permissive;
+pub use permissive::*;
+mod ray_cast;
+pub use ray_cast::*;
 mod slope;
 pub use slope::*;
 mod shadowcast;
 pub use shadowcast::*;
+mod vision_cone;
+pub use vision_cone::*;
 
 // TODO: Add more Fov Algorithms: http://www.adammil.net/blog/v125_Roguelike_Vision_Algorithms.html
 // TODO: Adam
-// TODO: Ray casting
-// TODO: Diamond walls (point-to-tile or point-to-point)
 // TODO: Half-width walls (point-to-tile or point-to-point)
-// TODO: Permissive field of view (tile-to-tile)
-// TODO: Digital field of view (diamond-to-diamond)
 
 /// Different algorithms for computing field of view
 pub enum Fov {
@@ -29,6 +44,20 @@ pub enum Fov {
     Shadowcast,
     /// Use the Shadowcast algorithm for computing field of view, but only in a single direction
     ShadowcastDirection(Direction),
+    /// Use the Shadowcast algorithm for computing field of view, but only within a cone of vision
+    ShadowcastCone(VisionCone),
+    /// Use the Adams algorithm for computing field of view, but only within a cone of vision
+    AdamsCone(VisionCone),
+    /// Use the Precise Permissive algorithm for computing a symmetric field of view
+    Permissive,
+    /// Use digital lines for computing a symmetric field of view
+    Digital,
+    /// Use the Diamond Walls algorithm for computing field of view
+    DiamondWalls,
+    /// Use ray casting for computing field of view
+    RayCast,
+    /// Use ray casting for computing field of view, with the specified perimeter and ray density
+    RayCastDensity(RayPerimeter, u32),
 }
 
 impl Fov {
@@ -51,6 +80,54 @@ impl Fov {
                 *direction,
                 pass_through_data,
             ),
+            Self::ShadowcastCone(cone) => {
+                Shadowcast::compute_cone(origin, range, provider, *cone, pass_through_data)
+            }
+            Self::AdamsCone(cone) => {
+                AdamsFov::compute_cone(origin, range, provider, *cone, pass_through_data)
+            }
+            Self::Permissive => {
+                PermissiveFov::compute_fov(origin, range, provider, pass_through_data)
+            }
+            Self::Digital => DigitalFov::compute_fov(origin, range, provider, pass_through_data),
+            Self::DiamondWalls => {
+                DiamondWallsFov::compute_fov(origin, range, provider, pass_through_data)
+            }
+            Self::RayCast => RayCastFov::compute_fov(origin, range, provider, pass_through_data),
+            Self::RayCastDensity(perimeter, density) => RayCastFov::compute_with_density(
+                origin,
+                range,
+                *perimeter,
+                *density,
+                provider,
+                pass_through_data,
+            ),
+        }
+    }
+
+    /// Computes the field of view for the specified origin, range, and provider, marking every
+    /// visible position in the receiver.
+    pub fn compute_into<FovRange: Into<u32>, T>(
+        &self,
+        origin: IVec2,
+        range: FovRange,
+        provider: &mut impl FovProvider<T>,
+        receiver: &mut impl FovReceiver,
+        pass_through_data: T,
+    ) {
+        let range = range.into();
+        match self {
+            Self::Adams => {
+                AdamsFov::compute_fov_into(origin, range, provider, receiver, pass_through_data)
+            }
+            Self::Shadowcast => {
+                Shadowcast::compute_fov_into(origin, range, provider, receiver, pass_through_data)
+            }
+            _ => {
+                for position in self.compute(origin, range, provider, pass_through_data) {
+                    receiver.set_visible(position);
+                }
+            }
         }
     }
 
@@ -67,3 +144,187 @@ impl Fov {
         Self::compute(self, origin, range, provider, pass_through_data).contains(&target)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    // hand-drawn maps, `@` marks the origin and `#` the walls
+    const OPEN_ROOM: &str = "\
+#######
+#.....#
+#..@..#
+#.....#
+#######";
+
+    const PILLAR: &str = "\
+###########
+#.........#
+#.@.#.....#
+#.........#
+###########";
+
+    const DIAGONAL_WALL: &str = "\
+#######
+#@.#..#
+#.#...#
+##....#
+#.....#
+#######";
+
+    const CORRIDOR: &str = "\
+###########
+#@........#
+#####.#####
+#####.#####
+###########";
+
+    fn render(map: &str, fov: &Fov) -> String {
+        let rows = map.lines().collect::<Vec<_>>();
+        let dimensions = UVec2::new(rows[0].len() as u32, rows.len() as u32);
+        let cells = rows.iter().flat_map(|row| row.chars()).collect::<Vec<_>>();
+        let grid = Grid::new(dimensions, cells);
+        let origin = grid.point_iter().find(|p| grid[*p] == '@').unwrap();
+
+        let mut provider = GridFovProvider::new(&grid, |tile: &char| *tile == '#');
+        let visible = fov.compute(origin, 10_u32, &mut provider, ());
+
+        rows.iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.chars()
+                    .enumerate()
+                    .map(|(x, tile)| {
+                        if visible.contains(&IVec2::new(x as i32, y as i32)) {
+                            tile
+                        } else {
+                            ' '
+                        }
+                    })
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim_end()
+            .to_string()
+    }
+
+    fn check(fov: Fov, expected: [&str; 4]) {
+        for (map, expected) in [OPEN_ROOM, PILLAR, DIAGONAL_WALL, CORRIDOR]
+            .into_iter()
+            .zip(expected)
+        {
+            assert_eq!(render(map, &fov), expected, "\n{map}");
+        }
+    }
+
+    #[test]
+    fn permissive() {
+        check(
+            Fov::Permissive,
+            [
+                OPEN_ROOM,
+                "\
+###########
+#.........#
+#.@.#
+#.........#
+###########",
+                "\
+####
+#@.#
+#.#...#
+##. ..#
+  .. .#
+  ##",
+                "\
+###########
+#@........#
+#####.#####",
+            ],
+        );
+    }
+
+    #[test]
+    fn digital() {
+        check(
+            Fov::Digital,
+            [
+                OPEN_ROOM,
+                "\
+###########
+#.........#
+#.@.#
+#.........#
+###########",
+                "\
+####
+#@.#
+#.#...#
+##. ..#
+  .. .#
+  ### #",
+                "\
+###########
+#@........#
+#####.#####",
+            ],
+        );
+    }
+
+    #[test]
+    fn diamond_walls() {
+        check(
+            Fov::DiamondWalls,
+            [
+                OPEN_ROOM,
+                "\
+#########
+#........
+#.@.#
+#........
+#########",
+                "\
+####
+#@.#
+#.#...#
+##. ..#
+  ..  #
+  ##",
+                "\
+###########
+#@........#
+#####.#####",
+            ],
+        );
+    }
+
+    #[test]
+    fn ray_cast() {
+        check(
+            Fov::RayCast,
+            [
+                OPEN_ROOM,
+                "\
+#######
+#.....
+#.@.#
+#.....
+#######",
+                "\
+####
+#@.#
+#.#..
+##. ..#
+  .. .#
+   ## #",
+                "\
+#######
+#@........#
+#####.#",
+            ],
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use crate::prelude::*;
+
+mod view;
+use view::*;
+
+/// Computes the field of view using the precise permissive algorithm.
+/// [Precise Permissive Fov](http://www.roguebasin.com/index.php/Precise_Permissive_Field_of_View)
+///
+/// A tile is visible if any line from any point of the origin tile reaches any point of it
+/// without passing through an opaque tile. This makes the field of view symmetric: if a tile
+/// can see another, it is seen by it as well.
+pub struct PermissiveFov;
+
+impl FovAlgorithm for PermissiveFov {
+    fn compute_fov<T>(
+        origin: IVec2,
+        range: u32,
+        provider: &mut impl FovProvider<T>,
+        mut pass_through_data: T,
+    ) -> HashSet<IVec2> {
+        let mut visible_points = HashSet::with_capacity(((range * 2) * (range * 2)) as usize);
+
+        visible_points.insert(origin);
+
+        for quadrant in [
+            IVec2::new(1, 1),
+            IVec2::new(-1, 1),
+            IVec2::new(-1, -1),
+            IVec2::new(1, -1),
+        ] {
+            Self::compute_quadrant(
+                origin,
+                quadrant,
+                range as i32,
+                provider,
+                &mut pass_through_data,
+                &mut visible_points,
+            );
+        }
+
+        visible_points
+    }
+}
+
+impl PermissiveFov {
+    // tiles are visited in diagonals moving away from the origin, from the shallow end to the
+    // steep end, so every view is visited in order as well.
+    fn compute_quadrant<T>(
+        origin: IVec2,
+        quadrant: IVec2,
+        range: i32,
+        provider: &mut impl FovProvider<T>,
+        pass_through_data: &mut T,
+        visible_points: &mut HashSet<IVec2>,
+    ) {
+        let mut bumps = Vec::new();
+        let mut views = vec![View::new(
+            SightLine::new(IVec2::new(0, 1), IVec2::new(range, 0)),
+            SightLine::new(IVec2::new(1, 0), IVec2::new(0, range)),
+        )];
+
+        for i in 1..=range * 2 {
+            let mut view_index = 0;
+            for j in (i - range).max(0)..=i.min(range) {
+                if view_index >= views.len() {
+                    break;
+                }
+
+                Self::visit_tile(
+                    IVec2::new(i - j, j),
+                    origin,
+                    quadrant,
+                    range,
+                    &mut views,
+                    &mut view_index,
+                    &mut bumps,
+                    provider,
+                    pass_through_data,
+                    visible_points,
+                );
+            }
+
+            if views.is_empty() {
+                break;
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn visit_tile<T>(
+        tile: IVec2,
+        origin: IVec2,
+        quadrant: IVec2,
+        range: i32,
+        views: &mut Vec<View>,
+        view_index: &mut usize,
+        bumps: &mut Vec<Bump>,
+        provider: &mut impl FovProvider<T>,
+        pass_through_data: &mut T,
+        visible_points: &mut HashSet<IVec2>,
+    ) {
+        let top_left = tile + IVec2::Y;
+        let bottom_right = tile + IVec2::X;
+
+        // skip the views that are entirely shallower than this tile
+        while *view_index < views.len()
+            && views[*view_index]
+                .steep_line
+                .is_below_or_colinear(bottom_right)
+        {
+            *view_index += 1;
+        }
+
+        // the tile falls between views
+        if *view_index == views.len()
+            || views[*view_index]
+                .shallow_line
+                .is_above_or_colinear(top_left)
+        {
+            return;
+        }
+
+        let position = origin + tile * quadrant;
+        if tile.x * tile.x + tile.y * tile.y <= range * range {
+            visible_points.insert(position);
+        }
+
+        if !provider.is_opaque(position, pass_through_data) {
+            return;
+        }
+
+        let index = *view_index;
+        let view = views[index];
+        let blocks_shallow = view.shallow_line.is_above(bottom_right);
+        let blocks_steep = view.steep_line.is_below(top_left);
+
+        match (blocks_shallow, blocks_steep) {
+            // the tile covers the whole view
+            (true, true) => {
+                views.remove(index);
+            }
+            (true, false) => {
+                views[index].add_shallow_bump(top_left, bumps);
+                Self::check_view(views, index);
+            }
+            (false, true) => {
+                views[index].add_steep_bump(bottom_right, bumps);
+                Self::check_view(views, index);
+            }
+            // the tile splits the view in two
+            (false, false) => {
+                views.insert(index, view);
+                views[index].add_steep_bump(bottom_right, bumps);
+                let steeper = if Self::check_view(views, index) {
+                    index + 1
+                } else {
+                    index
+                };
+                views[steeper].add_shallow_bump(top_left, bumps);
+                Self::check_view(views, steeper);
+            }
+        }
+    }
+
+    // removes the view if it has closed, returns true if the view is still open
+    fn check_view(views: &mut Vec<View>, index: usize) -> bool {
+        if views[index].is_closed() {
+            views.remove(index);
+            false
+        } else {
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn open_room() {
+        let grid = Grid::new_copy(UVec2::new(11, 11), false);
+        let mut provider = GridFovProvider::new(&grid, |opaque: &bool| *opaque);
+        let origin = IVec2::new(5, 5);
+        let visible = Fov::Permissive.compute(origin, 5_u32, &mut provider, ());
+
+        for position in grid.point_iter() {
+            let offset = position - origin;
+            assert_eq!(
+                visible.contains(&position),
+                offset.x * offset.x + offset.y * offset.y <= 25,
+            );
+        }
+    }
+
+    #[test]
+    fn wall_blocks_sight() {
+        let mut grid = Grid::new_copy(UVec2::new(11, 11), false);
+        for y in 0..11 {
+            grid[IVec2::new(7, y)] = true;
+        }
+        let mut provider = GridFovProvider::new(&grid, |opaque: &bool| *opaque);
+        let visible = Fov::Permissive.compute(IVec2::new(5, 5), 8_u32, &mut provider, ());
+
+        assert!(visible.contains(&IVec2::new(7, 0)));
+        assert!(visible.contains(&IVec2::new(7, 10)));
+        assert!(visible.iter().all(|position| position.x <= 7));
+    }
+
+    #[cfg(all(feature = "bitgrid", feature = "random"))]
+    #[test]
+    fn symmetry() {
+        let mut rng = StdRng::seed_from_u64(0xf0f);
+        let dimensions = UVec2::new(16, 16);
+
+        for _ in 0..20 {
+            let cells = (0..dimensions.size()).map(|_| rng.one_in(4_u32)).collect();
+            let grid = BitGrid::new(dimensions, cells);
+            let mut provider = GridFovProvider::new(&grid, |opaque: &bool| *opaque);
+
+            let floors = grid
+                .point_iter()
+                .filter(|position| !grid.get_unchecked(position.as_uvec2()))
+                .collect::<Vec<_>>();
+            let fovs = floors
+                .iter()
+                .map(|origin| Fov::Permissive.compute(*origin, 8_u32, &mut provider, ()))
+                .collect::<Vec<_>>();
+
+            for (a, fov_a) in floors.iter().zip(fovs.iter()) {
+                for (b, fov_b) in floors.iter().zip(fovs.iter()) {
+                    assert_eq!(fov_a.contains(b), fov_b.contains(a), "{a} and {b}");
+                }
+            }
+        }
+    }
+}
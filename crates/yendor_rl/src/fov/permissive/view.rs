@@ -0,0 +1,125 @@
+use crate::prelude::*;
+
+/// A line from a point near the origin to a point further out, in quadrant coordinates.
+#[derive(Clone, Copy)]
+pub struct SightLine {
+    pub near: IVec2,
+    pub far: IVec2,
+}
+
+impl SightLine {
+    pub const fn new(near: IVec2, far: IVec2) -> Self {
+        Self { near, far }
+    }
+
+    // positive when the point is above the line, negative when it is below
+    const fn relative_slope(&self, point: IVec2) -> i32 {
+        (self.far.y - self.near.y) * (self.far.x - point.x)
+            - (self.far.x - self.near.x) * (self.far.y - point.y)
+    }
+
+    /// line is below the point
+    pub const fn is_below(&self, point: IVec2) -> bool {
+        self.relative_slope(point) > 0
+    }
+
+    /// line is below or passes through the point
+    pub const fn is_below_or_colinear(&self, point: IVec2) -> bool {
+        self.relative_slope(point) >= 0
+    }
+
+    /// line is above the point
+    pub const fn is_above(&self, point: IVec2) -> bool {
+        self.relative_slope(point) < 0
+    }
+
+    /// line is above or passes through the point
+    pub const fn is_above_or_colinear(&self, point: IVec2) -> bool {
+        self.relative_slope(point) <= 0
+    }
+
+    /// line passes through the point
+    pub const fn is_colinear(&self, point: IVec2) -> bool {
+        self.relative_slope(point) == 0
+    }
+
+    /// both lines lie on the same infinite line
+    pub const fn is_line_colinear(&self, other: &Self) -> bool {
+        self.is_colinear(other.near) && self.is_colinear(other.far)
+    }
+}
+
+/// A corner of an opaque tile a view had to bend around.
+#[derive(Clone, Copy)]
+pub struct Bump {
+    pub point: IVec2,
+    /// The previous bump on the same side of the view.
+    pub parent: Option<usize>,
+}
+
+/// The area between two lines that is still visible.
+///
+/// Bumps are stored in a shared arena and referenced by index, every view keeps the chain of
+/// bumps on each of its sides.
+#[derive(Clone, Copy)]
+pub struct View {
+    pub shallow_line: SightLine,
+    pub steep_line: SightLine,
+    pub shallow_bump: Option<usize>,
+    pub steep_bump: Option<usize>,
+}
+
+impl View {
+    pub const fn new(shallow_line: SightLine, steep_line: SightLine) -> Self {
+        Self {
+            shallow_line,
+            steep_line,
+            shallow_bump: None,
+            steep_bump: None,
+        }
+    }
+
+    // the shallow line must stay above the corner, pivot it around the steep bumps it crosses
+    pub fn add_shallow_bump(&mut self, point: IVec2, bumps: &mut Vec<Bump>) {
+        self.shallow_line.far = point;
+        bumps.push(Bump {
+            point,
+            parent: self.shallow_bump,
+        });
+        self.shallow_bump = Some(bumps.len() - 1);
+
+        let mut current = self.steep_bump;
+        while let Some(index) = current {
+            let bump = bumps[index];
+            if self.shallow_line.is_above(bump.point) {
+                self.shallow_line.near = bump.point;
+            }
+            current = bump.parent;
+        }
+    }
+
+    // the steep line must stay below the corner, pivot it around the shallow bumps it crosses
+    pub fn add_steep_bump(&mut self, point: IVec2, bumps: &mut Vec<Bump>) {
+        self.steep_line.far = point;
+        bumps.push(Bump {
+            point,
+            parent: self.steep_bump,
+        });
+        self.steep_bump = Some(bumps.len() - 1);
+
+        let mut current = self.shallow_bump;
+        while let Some(index) = current {
+            let bump = bumps[index];
+            if self.steep_line.is_below(bump.point) {
+                self.steep_line.near = bump.point;
+            }
+            current = bump.parent;
+        }
+    }
+
+    /// A view is closed once both lines collapse onto a line through a corner of the origin.
+    pub const fn is_closed(&self) -> bool {
+        self.shallow_line.is_line_colinear(&self.steep_line)
+            && (self.shallow_line.is_colinear(IVec2::Y) || self.shallow_line.is_colinear(IVec2::X))
+    }
+}
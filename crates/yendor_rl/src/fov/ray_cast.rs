@@ -0,0 +1,116 @@
+use crate::prelude::*;
+
+/// The perimeter [`RayCastFov`] casts its rays towards.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RayPerimeter {
+    /// Cast a ray towards every cell on the border of a square, reaching every tile in range.
+    #[default]
+    Square,
+    /// Cast a ray towards every cell on the circumference of a [`Circle`], fewer rays but tiles
+    /// in between them may be missed.
+    Circle,
+}
+
+/// Computes the field of view by casting rays from the origin.
+/// [Ray Casting](http://www.adammil.net/blog/v125_Roguelike_Vision_Algorithms.html#raycode)
+///
+/// A [`BresenhamLineIter`] is traced from the origin to every cell on the perimeter of the range,
+/// stopping at the first opaque tile. This is the cheapest algorithm, but it is not symmetric
+/// and walls cast ragged shadows. Casting towards a larger perimeter with
+/// [`RayCastFov::compute_with_density`] smooths them out.
+pub struct RayCastFov;
+
+impl FovAlgorithm for RayCastFov {
+    fn compute_fov<T>(
+        origin: IVec2,
+        range: u32,
+        provider: &mut impl FovProvider<T>,
+        pass_through_data: T,
+    ) -> HashSet<IVec2> {
+        Self::compute_with_density(
+            origin,
+            range,
+            RayPerimeter::default(),
+            1,
+            provider,
+            pass_through_data,
+        )
+    }
+}
+
+impl RayCastFov {
+    /// Computes the field of view, casting rays towards every cell on a perimeter `density` times
+    /// the size of the range. A density of `1` casts one ray per cell on the edge of the range,
+    /// the rays still stop at the range.
+    pub fn compute_with_density<T>(
+        origin: IVec2,
+        range: u32,
+        perimeter: RayPerimeter,
+        density: u32,
+        provider: &mut impl FovProvider<T>,
+        mut pass_through_data: T,
+    ) -> HashSet<IVec2> {
+        let mut visible_points = HashSet::with_capacity(((range * 2) * (range * 2)) as usize);
+
+        visible_points.insert(origin);
+
+        let radius = range * density.max(1);
+        let targets = match perimeter {
+            RayPerimeter::Circle => Circle::new(origin, radius).get_circumfrence(),
+            RayPerimeter::Square => Self::square(origin, radius as i32),
+        };
+
+        let range = range as i32;
+        for target in targets {
+            for position in BresenhamLineInclusiveIter::new(origin, target).skip(1) {
+                let offset = position - origin;
+                if offset.x * offset.x + offset.y * offset.y > range * range {
+                    break;
+                }
+
+                visible_points.insert(position);
+                if provider.is_opaque(position, &mut pass_through_data) {
+                    break;
+                }
+            }
+        }
+
+        visible_points
+    }
+
+    fn square(origin: IVec2, radius: i32) -> HashSet<IVec2> {
+        let mut border = HashSet::with_capacity((radius * 8).max(1) as usize);
+        for i in -radius..=radius {
+            border.insert(origin + IVec2::new(i, -radius));
+            border.insert(origin + IVec2::new(i, radius));
+            border.insert(origin + IVec2::new(-radius, i));
+            border.insert(origin + IVec2::new(radius, i));
+        }
+        border
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    struct Open;
+    impl FovProvider<()> for Open {
+        fn is_opaque(&mut self, _position: IVec2, _pass_through_data: &mut ()) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn reaches_every_tile() {
+        for range in [1, 5, 16, 24] {
+            let visible = RayCastFov::compute_fov(IVec2::ZERO, range, &mut Open, ());
+            let range = range as i32;
+            let in_range = Rectangle::new(IVec2::splat(-range), IVec2::splat(range))
+                .into_iter()
+                .filter(|p| p.x * p.x + p.y * p.y <= range * range)
+                .collect::<HashSet<_>>();
+            assert_eq!(visible, in_range);
+        }
+    }
+}
@@ -14,19 +14,33 @@ impl FovAlgorithm for Shadowcast {
         origin: IVec2,
         range: u32,
         provider: &mut impl FovProvider<T>,
-        mut pass_through_data: T,
+        pass_through_data: T,
     ) -> HashSet<IVec2> {
         let mut visible_points = HashSet::with_capacity(((range * 2) * (range * 2)) as usize);
+        Self::compute_fov_into(
+            origin,
+            range,
+            provider,
+            &mut visible_points,
+            pass_through_data,
+        );
+        visible_points
+    }
 
-        visible_points.insert(origin);
+    fn compute_fov_into<T>(
+        origin: IVec2,
+        range: u32,
+        provider: &mut impl FovProvider<T>,
+        receiver: &mut impl FovReceiver,
+        mut pass_through_data: T,
+    ) {
+        receiver.set_visible(origin);
 
         CardinalDirection::all().for_each(|direction| {
             let mut quadrant = Quadrant::new(direction, origin, provider, &mut pass_through_data);
             let mut first_row = Row::new(1, Slope::new(-1, 1), Slope::new(1, 1));
-            Self::scan_recursive(range, &mut quadrant, &mut first_row, &mut visible_points);
+            Self::scan_recursive(range, &mut quadrant, &mut first_row, receiver);
         });
-
-        visible_points
     }
 }
 
@@ -49,11 +63,59 @@ impl Shadowcast {
         visible_points
     }
 
+    /// Computes the field of view within a cone of vision.
+    pub fn compute_cone<T>(
+        origin: IVec2,
+        range: u32,
+        provider: &mut impl FovProvider<T>,
+        cone: VisionCone,
+        mut pass_through_data: T,
+    ) -> HashSet<IVec2> {
+        let mut visible_points = HashSet::with_capacity(((range * 2) * (range * 2)) as usize);
+        visible_points.insert(origin);
+
+        CardinalDirection::all().for_each(|direction| {
+            let mut quadrant = Quadrant::new(direction, origin, provider, &mut pass_through_data);
+            let (forward, side) = quadrant.axes();
+            for (start_slope, end_slope) in
+                cone.clip(forward, side, Slope::new(-1, 1), Slope::new(1, 1))
+            {
+                let mut first_row = Row::new(1, start_slope, end_slope);
+                Self::scan_recursive(range, &mut quadrant, &mut first_row, &mut visible_points);
+            }
+        });
+
+        visible_points
+    }
+
+    /// Computes the field of view through translucent tiles, returning how clearly each visible
+    /// position can be seen, from `1.0` for a clear view down to `0.0`.
+    ///
+    /// The view is dimmed by the opacity of every tile it passes through, a tile itself is seen
+    /// with the strength of the view reaching it.
+    pub fn compute_translucent<T>(
+        origin: IVec2,
+        range: u32,
+        provider: &mut impl TranslucentFovProvider<T>,
+        mut pass_through_data: T,
+    ) -> HashMap<IVec2, f32> {
+        let mut visibility = HashMap::with_capacity(((range * 2) * (range * 2)) as usize);
+        visibility.insert(origin, 1.0);
+
+        CardinalDirection::all().for_each(|direction| {
+            let mut quadrant = Quadrant::new(direction, origin, provider, &mut pass_through_data);
+            let mut first_row = Row::new(1, Slope::new(-1, 1), Slope::new(1, 1));
+            Self::scan_translucent(range, &mut quadrant, &mut first_row, 1.0, &mut visibility);
+        });
+
+        visibility
+    }
+
     fn scan_recursive<T>(
         range: u32,
-        quadrant: &mut Quadrant<T>,
+        quadrant: &mut Quadrant<T, impl FovProvider<T>>,
         row: &mut Row,
-        visible_points: &mut HashSet<IVec2>,
+        receiver: &mut impl FovReceiver,
     ) {
         let mut prev_tile = None;
         for tile in row.tiles() {
@@ -63,7 +125,7 @@ impl Shadowcast {
 
             // Should we reveal the tile?
             if quadrant.is_opaque(tile) | row.is_symmetric(tile) {
-                quadrant.set_visible(visible_points, tile);
+                quadrant.set_visible(receiver, tile);
             }
 
             // handle the current row based on vision angles around the previous tile
@@ -76,7 +138,7 @@ impl Shadowcast {
                 if quadrant.is_clear(prev_tile) & quadrant.is_opaque(tile) {
                     let mut next_row = row.next();
                     next_row.calc_ending_slope(tile);
-                    Self::scan_recursive(range, quadrant, &mut next_row, visible_points);
+                    Self::scan_recursive(range, quadrant, &mut next_row, receiver);
                 }
             }
 
@@ -87,8 +149,125 @@ impl Shadowcast {
         // if our last tile was floor, we can see down another row
         if let Some(prev_tile) = prev_tile {
             if quadrant.is_clear(prev_tile) {
-                Self::scan_recursive(range, quadrant, &mut row.next(), visible_points);
+                Self::scan_recursive(range, quadrant, &mut row.next(), receiver);
             }
         }
     }
+
+    // like `scan_recursive`, but every run of tiles sharing an opacity casts its own row, dimmed
+    // by the opacity of the run
+    fn scan_translucent<T>(
+        range: u32,
+        quadrant: &mut Quadrant<T, impl TranslucentFovProvider<T>>,
+        row: &mut Row,
+        strength: f32,
+        visibility: &mut HashMap<IVec2, f32>,
+    ) {
+        let mut prev_opacity = None;
+        for tile in row.tiles() {
+            if quadrant.distance_squared(tile) > (range as u64 * range as u64) {
+                continue;
+            }
+
+            let opacity = quadrant.opacity(tile);
+            if opacity >= 1.0 || row.is_symmetric(tile) {
+                let seen = visibility.entry(quadrant.position(tile)).or_insert(0.0);
+                *seen = seen.max(strength);
+            }
+
+            // did the opacity change? the previous run can see down another row
+            if let Some(prev_opacity) = prev_opacity {
+                if opacity != prev_opacity {
+                    if prev_opacity < 1.0 {
+                        let mut next_row = row.next();
+                        next_row.calc_ending_slope(tile);
+                        Self::scan_translucent(
+                            range,
+                            quadrant,
+                            &mut next_row,
+                            strength * (1.0 - prev_opacity),
+                            visibility,
+                        );
+                    }
+                    row.calc_starting_slope(tile);
+                }
+            }
+
+            prev_opacity = Some(opacity);
+        }
+
+        if let Some(prev_opacity) = prev_opacity {
+            if prev_opacity < 1.0 {
+                Self::scan_translucent(
+                    range,
+                    quadrant,
+                    &mut row.next(),
+                    strength * (1.0 - prev_opacity),
+                    visibility,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    // opacity of every tile, anything outside the grid is opaque
+    struct Provider(Grid<f32>);
+    impl TranslucentFovProvider<()> for Provider {
+        fn opacity(&mut self, position: IVec2, _pass_through_data: &mut ()) -> f32 {
+            if position.is_valid(self.0.dimensions()) {
+                self.0[position]
+            } else {
+                1.0
+            }
+        }
+    }
+    impl FovProvider<()> for Provider {
+        fn is_opaque(&mut self, position: IVec2, pass_through_data: &mut ()) -> bool {
+            self.opacity(position, pass_through_data) >= 1.0
+        }
+    }
+
+    #[test]
+    fn translucent() {
+        // a corridor with two clouds of smoke
+        let mut grid = Grid::new_copy(UVec2::new(10, 3), 1.0);
+        for x in 0..10 {
+            grid[IVec2::new(x, 1)] = 0.0;
+        }
+        grid[IVec2::new(3, 1)] = 0.5;
+        grid[IVec2::new(5, 1)] = 0.5;
+        grid[IVec2::new(8, 1)] = 1.0;
+
+        let visibility =
+            Shadowcast::compute_translucent(IVec2::new(0, 1), 12, &mut Provider(grid), ());
+        let corridor = (0..10)
+            .map(|x| visibility.get(&IVec2::new(x, 1)).copied().unwrap_or(0.0))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            corridor,
+            [1.0, 1.0, 1.0, 1.0, 0.5, 0.5, 0.25, 0.25, 0.25, 0.0]
+        );
+    }
+
+    #[test]
+    fn opaque_matches_shadowcast() {
+        let grid = Grid::new_fn(UVec2::new(20, 20), |(_, p)| {
+            if (p.x * 7 + p.y * 13) % 5 == 0 {
+                1.0
+            } else {
+                0.0
+            }
+        });
+        let mut provider = Provider(grid);
+        let origin = IVec2::new(9, 11);
+
+        let visibility = Shadowcast::compute_translucent(origin, 8, &mut provider, ());
+        let expected = Shadowcast::compute_fov(origin, 8, &mut provider, ());
+        assert_eq!(visibility.keys().copied().collect::<HashSet<_>>(), expected);
+        assert!(visibility.values().all(|strength| *strength == 1.0));
+    }
 }
@@ -1,17 +1,17 @@
 use crate::prelude::*;
 
-pub struct Quadrant<'a, T> {
+pub struct Quadrant<'a, T, P: ?Sized> {
     origin: IVec2,
     direction: Direction,
     pass_through_data: &'a mut T,
-    provider: &'a mut dyn FovProvider<T>,
+    provider: &'a mut P,
 }
 
-impl<'a, T> Quadrant<'a, T> {
-    pub fn new(
+impl<'a, T, P: ?Sized> Quadrant<'a, T, P> {
+    pub const fn new(
         direction: Direction,
         origin: IVec2,
-        provider: &'a mut dyn FovProvider<T>,
+        provider: &'a mut P,
         pass_through_data: &'a mut T,
     ) -> Self {
         Self {
@@ -35,15 +35,27 @@ impl<'a, T> Quadrant<'a, T> {
         }
     }
 
+    // the directions a tile's depth and column point in
+    pub const fn axes(&self) -> (IVec2, IVec2) {
+        (self.transform(IVec2::X), self.transform(IVec2::Y))
+    }
+
     pub const fn distance_squared(&self, tile: IVec2) -> u64 {
         (tile.x * tile.x + tile.y * tile.y) as u64
     }
 
     // mark this tile as visible
-    pub fn set_visible(&mut self, visible_points: &mut HashSet<IVec2>, tile: IVec2) {
-        visible_points.insert(self.origin + self.transform(tile));
+    pub fn set_visible(&mut self, receiver: &mut impl FovReceiver, tile: IVec2) {
+        receiver.set_visible(self.origin + self.transform(tile));
     }
 
+    // the position of this tile on the map
+    pub fn position(&self, tile: IVec2) -> IVec2 {
+        self.origin + self.transform(tile)
+    }
+}
+
+impl<'a, T, P: ?Sized + FovProvider<T>> Quadrant<'a, T, P> {
     // check if this tile is opaque
     pub fn is_opaque(&mut self, tile: IVec2) -> bool {
         self.provider
@@ -54,3 +66,12 @@ impl<'a, T> Quadrant<'a, T> {
         !self.is_opaque(tile)
     }
 }
+
+impl<'a, T, P: ?Sized + TranslucentFovProvider<T>> Quadrant<'a, T, P> {
+    // how much of the view through this tile is blocked
+    pub fn opacity(&mut self, tile: IVec2) -> f32 {
+        self.provider
+            .opacity(self.origin + self.transform(tile), self.pass_through_data)
+            .clamp(0.0, 1.0)
+    }
+}
@@ -0,0 +1,160 @@
+use crate::prelude::*;
+
+// the denominator of the slopes approximating the edges of a cone
+const SLOPE_PRECISION: i32 = 1 << 12;
+
+/// A cone of vision, limiting a field of view to the tiles within an angle of a facing.
+///
+/// Angles are in degrees, counterclockwise from `East`, so `North` is `90`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VisionCone {
+    facing: f32,
+    half_angle: f32,
+}
+
+impl VisionCone {
+    /// Creates a new cone facing the angle and spreading `half_angle` to either side of it.
+    pub fn new(facing: f32, half_angle: f32) -> Self {
+        Self {
+            facing: facing.rem_euclid(360.0),
+            half_angle: half_angle.clamp(0.0, 180.0),
+        }
+    }
+
+    /// Creates a new cone facing the direction and spreading `half_angle` to either side of it.
+    pub fn from_direction(direction: Direction, half_angle: f32) -> Self {
+        let coord = direction.coord().as_vec2();
+        Self::new(coord.y.atan2(coord.x).to_degrees(), half_angle)
+    }
+
+    /// Returns the facing of the cone.
+    #[inline]
+    pub const fn facing(&self) -> f32 {
+        self.facing
+    }
+
+    /// Returns the angle the cone spreads to either side of its facing.
+    #[inline]
+    pub const fn half_angle(&self) -> f32 {
+        self.half_angle
+    }
+
+    /// Returns true if the offset from the origin of the cone lies within it.
+    pub fn contains(&self, offset: IVec2) -> bool {
+        if offset == IVec2::ZERO {
+            return true;
+        }
+
+        let angle = (offset.y as f64).atan2(offset.x as f64).to_degrees();
+        let difference = (angle - self.facing as f64 + 180.0).rem_euclid(360.0) - 180.0;
+        difference.abs() <= self.half_angle as f64 + f64::EPSILON.sqrt()
+    }
+
+    /// Clips the slopes `low..=high` of a scan to the cone, returning the ranges left to scan.
+    ///
+    /// A tile `(x, y)` of the scan lies at `forward * x + side * y` from the origin, with
+    /// `forward` and `side` perpendicular unit vectors.
+    pub(crate) fn clip(
+        &self,
+        forward: IVec2,
+        side: IVec2,
+        low: Slope,
+        high: Slope,
+    ) -> Vec<(Slope, Slope)> {
+        if self.half_angle >= 180.0 {
+            return vec![(low, high)];
+        }
+
+        // angles relative to `forward`, increasing towards `side`
+        let handedness = (forward.x * side.y - forward.y * side.x) as f64;
+        let forward_angle = (forward.y as f64).atan2(forward.x as f64).to_degrees();
+        let center = handedness * (self.facing as f64 - forward_angle);
+        let center = (center + 180.0).rem_euclid(360.0) - 180.0;
+        let half_angle = self.half_angle as f64;
+
+        let low_angle = low.value().atan().to_degrees();
+        let high_angle = high.value().atan().to_degrees();
+
+        let mut slopes = Vec::new();
+        for turn in [-360.0, 0.0, 360.0] {
+            let start = low_angle.max(center - half_angle + turn);
+            let end = high_angle.min(center + half_angle + turn);
+            if start > end {
+                continue;
+            }
+
+            let start = if start > low_angle {
+                Self::slope(start, f64::floor)
+            } else {
+                low
+            };
+            let end = if end < high_angle {
+                Self::slope(end, f64::ceil)
+            } else {
+                high
+            };
+            slopes.push((start, end));
+        }
+        slopes
+    }
+
+    // rounds outwards, so tiles right on the edge of the cone stay visible
+    fn slope(angle: f64, round: fn(f64) -> f64) -> Slope {
+        let value = angle.to_radians().tan() * SLOPE_PRECISION as f64;
+        Slope::new(round(value) as i32, SLOPE_PRECISION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    struct Open;
+    impl FovProvider<()> for Open {
+        fn is_opaque(&mut self, _position: IVec2, _pass_through_data: &mut ()) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn contains() {
+        let cone = VisionCone::from_direction(Direction::NORTH_EAST, 30.0);
+        assert_eq!(cone.facing(), 45.0);
+        assert!(cone.contains(IVec2::new(3, 3)));
+        assert!(cone.contains(IVec2::new(4, 2)));
+        assert!(!cone.contains(IVec2::new(4, 1)));
+        assert!(!cone.contains(IVec2::new(-3, -3)));
+
+        let cone = VisionCone::new(-180.0, 10.0);
+        assert_eq!(cone.facing(), 180.0);
+        assert!(cone.contains(IVec2::new(-5, 0)));
+        assert!(!cone.contains(IVec2::new(5, 0)));
+    }
+
+    #[test]
+    fn open_field() {
+        let range = 12;
+        let cones = Direction::all()
+            .flat_map(|direction| {
+                [0.0, 20.0, 45.0, 100.0, 160.0, 180.0]
+                    .map(|half_angle| VisionCone::from_direction(direction, half_angle))
+            })
+            .chain([
+                VisionCone::new(30.0, 25.0),
+                VisionCone::new(200.0, 70.0),
+                VisionCone::new(-100.0, 5.0),
+            ]);
+
+        for cone in cones {
+            let expected = Rectangle::new(IVec2::splat(-range), IVec2::splat(range))
+                .into_iter()
+                .filter(|p| p.x * p.x + p.y * p.y <= range * range && cone.contains(*p))
+                .collect::<HashSet<_>>();
+
+            for fov in [Fov::ShadowcastCone(cone), Fov::AdamsCone(cone)] {
+                let visible = fov.compute(IVec2::ZERO, range as u32, &mut Open, ());
+                assert_eq!(visible, expected, "{cone:?}");
+            }
+        }
+    }
+}
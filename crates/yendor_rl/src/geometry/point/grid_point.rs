@@ -68,12 +68,13 @@ pub trait GridPoint: Clone + Copy {
         }
     }
 
-    /// Chevbyshev distance between two points.
+    /// Chebyshev distance between two points, the number of king moves from one to the other.
+    /// Symmetric and defined for points on either side of each other, including negative ones.
     #[inline]
     fn distance(&self, point: impl GridPoint) -> u32 {
-        let start = self.as_uvec2();
-        let end = point.as_uvec2();
-        start.sub(end).max_element()
+        let start = self.as_ivec2();
+        let end = point.as_ivec2();
+        start.sub(end).abs().max_element() as u32
     }
 
     /// Returns an iterator over all points within the size.
@@ -111,3 +112,18 @@ impl GridPoint for Vec2 {
         self.y.floor() as i32
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn distance() {
+        let origin = IVec2::new(2, 3);
+        assert_eq!(origin.distance(IVec2::new(5, 4)), 3);
+        assert_eq!(IVec2::new(5, 4).distance(origin), 3);
+        assert_eq!(origin.distance(IVec2::new(1, -4)), 7);
+        assert_eq!(UVec2::new(0, 6).distance(UVec2::new(4, 1)), 5);
+        assert_eq!(origin.distance(origin), 0);
+    }
+}
@@ -23,12 +23,12 @@ impl Iterator for PointIterRowMajor {
     type Item = IVec2;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.coord.y == self.size.x as i32 {
+        if self.coord.y == self.size.y as i32 {
             return None;
         }
         let coord = self.coord;
         self.coord.x += 1;
-        if self.coord.x == self.size.y as i32 {
+        if self.coord.x == self.size.x as i32 {
             self.coord.x = 0;
             self.coord.y += 1;
         }
@@ -72,3 +72,24 @@ impl Iterator for AdjIterator {
         Some(p)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn row_major() {
+        let points = PointIterRowMajor::new(UVec2::new(3, 2)).collect::<Vec<_>>();
+        assert_eq!(
+            points,
+            [
+                IVec2::new(0, 0),
+                IVec2::new(1, 0),
+                IVec2::new(2, 0),
+                IVec2::new(0, 1),
+                IVec2::new(1, 1),
+                IVec2::new(2, 1),
+            ]
+        );
+    }
+}
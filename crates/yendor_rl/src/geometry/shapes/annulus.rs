@@ -0,0 +1,148 @@
+use crate::prelude::*;
+
+use super::shape::{border_points_within, is_border_point, points_within};
+
+/// A grid based ring, the cells of a circle outside of a smaller circle sharing its center.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Annulus {
+    center: IVec2,
+    inner_radius: u32,
+    outer_radius: u32,
+}
+
+impl Annulus {
+    /// Creates a new ring, containing the cells `inner_radius..=outer_radius` away from the
+    /// center. An inner radius of `0` fills the ring.
+    #[inline]
+    pub fn new(center: IVec2, inner_radius: u32, outer_radius: u32) -> Self {
+        Self {
+            center,
+            inner_radius: inner_radius.min(outer_radius),
+            outer_radius: inner_radius.max(outer_radius),
+        }
+    }
+}
+
+impl Annulus {
+    /// Get the center of the ring
+    #[inline]
+    pub const fn center(&self) -> IVec2 {
+        self.center
+    }
+
+    /// Get the radius of the hole in the ring
+    #[inline]
+    pub const fn inner_radius(&self) -> u32 {
+        self.inner_radius
+    }
+
+    /// Get the radius of the ring
+    #[inline]
+    pub const fn outer_radius(&self) -> u32 {
+        self.outer_radius
+    }
+
+    /// Get the smallest rectangle containing the ring
+    #[inline]
+    pub fn bounds(&self) -> Rectangle {
+        let radius = IVec2::splat(self.outer_radius as i32);
+        Rectangle::new(self.center - radius, self.center + radius)
+    }
+
+    // a cell is within the radius if its center is within the circle grown by half a cell
+    #[inline]
+    fn within(&self, position: IVec2, radius: u32) -> bool {
+        let offset = position - self.center;
+        let (x, y) = (offset.x as i64, offset.y as i64);
+        let diameter = 2 * radius as i64 + 1;
+        4 * (x * x + y * y) < diameter * diameter
+    }
+}
+
+impl Shape for Annulus {
+    #[inline]
+    fn get_count(&self) -> u32 {
+        self.iter().len() as u32
+    }
+
+    #[inline]
+    fn contains(&self, position: IVec2) -> bool {
+        self.within(position, self.outer_radius)
+            && (self.inner_radius == 0 || !self.within(position, self.inner_radius - 1))
+    }
+
+    #[inline]
+    fn get_positions(&self) -> HashSet<IVec2> {
+        self.iter().collect()
+    }
+
+    #[inline]
+    fn boxed_iter(&self) -> BoxedShapeIter {
+        Box::new(self.into_iter())
+    }
+}
+
+impl ShapeWithBorder for Annulus {
+    #[inline]
+    fn get_border_count(&self) -> usize {
+        border_points_within(self, self.bounds()).len()
+    }
+
+    #[inline]
+    fn border_contains(&self, point: IVec2) -> bool {
+        is_border_point(self, point)
+    }
+
+    #[inline]
+    fn get_border_positions(&self) -> HashSet<IVec2> {
+        border_points_within(self, self.bounds())
+    }
+}
+
+impl ShapeIter for Annulus {
+    type Iterator = std::vec::IntoIter<IVec2>;
+
+    #[inline]
+    fn iter(&self) -> Self::Iterator {
+        self.into_iter()
+    }
+}
+
+impl IntoIterator for Annulus {
+    type IntoIter = std::vec::IntoIter<IVec2>;
+    type Item = IVec2;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        points_within(&self, self.bounds())
+    }
+}
+
+impl From<Annulus> for BoxedShape {
+    fn from(value: Annulus) -> Self {
+        Box::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn ring() {
+        let ring = Annulus::new(IVec2::ZERO, 2, 3);
+        assert!(!ring.contains(IVec2::ZERO));
+        assert!(!ring.contains(IVec2::new(1, 1)));
+        assert!(ring.contains(IVec2::new(2, 0)));
+        assert!(ring.contains(IVec2::new(0, -3)));
+        assert!(!ring.contains(IVec2::new(3, 3)));
+
+        // every cell of the ring touches one of its edges
+        assert_eq!(ring.get_border_count(), ring.get_count() as usize);
+        assert_eq!(
+            Annulus::new(IVec2::ZERO, 0, 3).get_positions(),
+            Ellipse::new(IVec2::ZERO, UVec2::splat(3)).get_positions()
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use crate::prelude::*;
+
+/// The cells in one shape but not in another.
+#[derive(Clone)]
+pub struct Difference {
+    a: Arc<dyn Shape + Send + Sync>,
+    b: Arc<dyn Shape + Send + Sync>,
+}
+
+impl Difference {
+    /// Creates a new shape containing the cells in `a` but not in `b`.
+    pub fn new(a: impl Into<BoxedShape>, b: impl Into<BoxedShape>) -> Self {
+        Self {
+            a: Arc::from(a.into()),
+            b: Arc::from(b.into()),
+        }
+    }
+}
+
+impl Shape for Difference {
+    #[inline]
+    fn get_count(&self) -> u32 {
+        self.iter().count() as u32
+    }
+
+    #[inline]
+    fn contains(&self, position: IVec2) -> bool {
+        self.a.contains(position) && !self.b.contains(position)
+    }
+
+    #[inline]
+    fn get_positions(&self) -> HashSet<IVec2> {
+        self.iter().collect()
+    }
+
+    #[inline]
+    fn boxed_iter(&self) -> BoxedShapeIter {
+        self.iter()
+    }
+}
+
+impl ShapeIter for Difference {
+    type Iterator = BoxedShapeIter;
+
+    #[inline]
+    fn iter(&self) -> Self::Iterator {
+        let b = self.b.clone();
+        Box::new(
+            self.a
+                .boxed_iter()
+                .filter(move |position| !b.contains(*position)),
+        )
+    }
+}
+
+impl IntoIterator for Difference {
+    type IntoIter = BoxedShapeIter;
+    type Item = IVec2;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<Difference> for BoxedShape {
+    fn from(value: Difference) -> Self {
+        Box::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn holes() {
+        let line = Line::new(IVec2::ZERO, IVec2::new(4, 0));
+        let holes = HashSet::from_iter([IVec2::new(1, 0), IVec2::new(3, 0), IVec2::new(9, 9)]);
+        let difference = Difference::new(line, holes);
+
+        assert!(difference.contains(IVec2::new(2, 0)));
+        assert!(!difference.contains(IVec2::new(3, 0)));
+        assert!(!difference.contains(IVec2::new(9, 9)));
+        assert_eq!(difference.get_count(), 3);
+        assert_eq!(
+            difference.get_positions(),
+            HashSet::from_iter([IVec2::new(0, 0), IVec2::new(2, 0), IVec2::new(4, 0)])
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use crate::prelude::*;
+
+/// The cells in both of two shapes.
+#[derive(Clone)]
+pub struct Intersection {
+    a: Arc<dyn Shape + Send + Sync>,
+    b: Arc<dyn Shape + Send + Sync>,
+}
+
+impl Intersection {
+    /// Creates a new shape containing the cells in both `a` and `b`.
+    pub fn new(a: impl Into<BoxedShape>, b: impl Into<BoxedShape>) -> Self {
+        Self {
+            a: Arc::from(a.into()),
+            b: Arc::from(b.into()),
+        }
+    }
+}
+
+impl Shape for Intersection {
+    #[inline]
+    fn get_count(&self) -> u32 {
+        self.iter().count() as u32
+    }
+
+    #[inline]
+    fn contains(&self, position: IVec2) -> bool {
+        self.a.contains(position) && self.b.contains(position)
+    }
+
+    #[inline]
+    fn get_positions(&self) -> HashSet<IVec2> {
+        self.iter().collect()
+    }
+
+    #[inline]
+    fn boxed_iter(&self) -> BoxedShapeIter {
+        self.iter()
+    }
+}
+
+impl ShapeIter for Intersection {
+    type Iterator = BoxedShapeIter;
+
+    #[inline]
+    fn iter(&self) -> Self::Iterator {
+        let b = self.b.clone();
+        Box::new(
+            self.a
+                .boxed_iter()
+                .filter(move |position| b.contains(*position)),
+        )
+    }
+}
+
+impl IntoIterator for Intersection {
+    type IntoIter = BoxedShapeIter;
+    type Item = IVec2;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<Intersection> for BoxedShape {
+    fn from(value: Intersection) -> Self {
+        Box::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn area_of_effect() {
+        let caster = IVec2::new(4, 4);
+        let blast = Circle::new(caster, 3_u32);
+        let mut in_sight = blast.get_positions();
+        in_sight.retain(|position| position.x <= 5);
+
+        let shape = Intersection::new(
+            Difference::new(blast, HashSet::from_iter([caster])),
+            in_sight.clone(),
+        );
+        let mut expected = in_sight.clone();
+        expected.remove(&caster);
+        assert_eq!(shape.get_positions(), expected);
+        assert_eq!(shape.get_count() as usize, expected.len());
+        assert!(!shape.contains(caster));
+        assert!(shape.contains(IVec2::new(5, 5)));
+        assert!(!shape.contains(IVec2::new(6, 4)));
+
+        let shifted = Translate::new(shape, IVec2::new(10, 0));
+        assert!(shifted.contains(IVec2::new(15, 5)));
+        assert!(!shifted.contains(IVec2::new(5, 5)));
+
+        let both = Union::new(shifted, blast);
+        assert_eq!(
+            both.get_count() as usize,
+            expected.len() + blast.get_count() as usize
+        );
+        assert_eq!(both.iter().count(), both.get_positions().len());
+    }
+}
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use crate::prelude::*;
+
+/// A shape moved by an offset.
+#[derive(Clone)]
+pub struct Translate {
+    shape: Arc<dyn Shape + Send + Sync>,
+    offset: IVec2,
+}
+
+impl Translate {
+    /// Creates a new shape containing the cells of `shape` moved by `offset`.
+    pub fn new(shape: impl Into<BoxedShape>, offset: IVec2) -> Self {
+        Self {
+            shape: Arc::from(shape.into()),
+            offset,
+        }
+    }
+
+    /// Get the offset the shape is moved by
+    #[inline]
+    pub const fn offset(&self) -> IVec2 {
+        self.offset
+    }
+}
+
+impl Shape for Translate {
+    #[inline]
+    fn get_count(&self) -> u32 {
+        self.shape.get_count()
+    }
+
+    #[inline]
+    fn contains(&self, position: IVec2) -> bool {
+        self.shape.contains(position - self.offset)
+    }
+
+    #[inline]
+    fn get_positions(&self) -> HashSet<IVec2> {
+        self.iter().collect()
+    }
+
+    #[inline]
+    fn boxed_iter(&self) -> BoxedShapeIter {
+        self.iter()
+    }
+}
+
+impl ShapeIter for Translate {
+    type Iterator = BoxedShapeIter;
+
+    #[inline]
+    fn iter(&self) -> Self::Iterator {
+        let offset = self.offset;
+        Box::new(
+            self.shape
+                .boxed_iter()
+                .map(move |position| position + offset),
+        )
+    }
+}
+
+impl IntoIterator for Translate {
+    type IntoIter = BoxedShapeIter;
+    type Item = IVec2;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<Translate> for BoxedShape {
+    fn from(value: Translate) -> Self {
+        Box::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn offset() {
+        let triangle = Triangle::new(IVec2::ZERO, IVec2::new(4, 0), IVec2::new(0, 4));
+        let offset = IVec2::new(5, -2);
+        let translated = Translate::new(triangle, offset);
+
+        assert_eq!(translated.offset(), offset);
+        assert!(translated.contains(IVec2::new(7, 0)));
+        assert!(!translated.contains(IVec2::new(2, 2)));
+        assert_eq!(translated.get_count(), triangle.get_count());
+        assert_eq!(
+            translated.get_positions(),
+            triangle.iter().map(|position| position + offset).collect()
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use crate::prelude::*;
+
+/// The cells in either of two shapes.
+#[derive(Clone)]
+pub struct Union {
+    a: Arc<dyn Shape + Send + Sync>,
+    b: Arc<dyn Shape + Send + Sync>,
+}
+
+impl Union {
+    /// Creates a new shape containing the cells in `a` or `b`.
+    pub fn new(a: impl Into<BoxedShape>, b: impl Into<BoxedShape>) -> Self {
+        Self {
+            a: Arc::from(a.into()),
+            b: Arc::from(b.into()),
+        }
+    }
+}
+
+impl Shape for Union {
+    #[inline]
+    fn get_count(&self) -> u32 {
+        self.iter().count() as u32
+    }
+
+    #[inline]
+    fn contains(&self, position: IVec2) -> bool {
+        self.a.contains(position) || self.b.contains(position)
+    }
+
+    #[inline]
+    fn get_positions(&self) -> HashSet<IVec2> {
+        self.iter().collect()
+    }
+
+    #[inline]
+    fn boxed_iter(&self) -> BoxedShapeIter {
+        self.iter()
+    }
+}
+
+impl ShapeIter for Union {
+    type Iterator = BoxedShapeIter;
+
+    // the cells of `b` already in `a` are skipped
+    #[inline]
+    fn iter(&self) -> Self::Iterator {
+        let a = self.a.clone();
+        Box::new(
+            self.a.boxed_iter().chain(
+                self.b
+                    .boxed_iter()
+                    .filter(move |position| !a.contains(*position)),
+            ),
+        )
+    }
+}
+
+impl IntoIterator for Union {
+    type IntoIter = BoxedShapeIter;
+    type Item = IVec2;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<Union> for BoxedShape {
+    fn from(value: Union) -> Self {
+        Box::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn is_send_sync<T: Send + Sync>(_: &T) {}
+
+    #[test]
+    fn overlap() {
+        let row = HashSet::from_iter([IVec2::new(0, 0), IVec2::new(1, 0), IVec2::new(2, 0)]);
+        let column = HashSet::from_iter([IVec2::new(2, 0), IVec2::new(2, 1)]);
+        let union = Union::new(row, column);
+        is_send_sync(&union);
+
+        assert!(union.contains(IVec2::new(0, 0)));
+        assert!(union.contains(IVec2::new(2, 1)));
+        assert!(!union.contains(IVec2::new(1, 1)));
+
+        // the shared cell is only visited once
+        assert_eq!(union.get_count(), 4);
+        assert_eq!(union.iter().count(), union.get_positions().len());
+    }
+}
@@ -0,0 +1,181 @@
+use crate::prelude::*;
+
+use super::shape::{border_points_within, is_border_point, points_within};
+
+/// A grid based cone, the cells of a circle within an angle of a facing, such as the breath of
+/// a dragon.
+///
+/// Angles are in degrees, counterclockwise from `East`, so `North` is `90`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Cone {
+    origin: IVec2,
+    facing: f32,
+    half_angle: f32,
+    radius: u32,
+}
+
+impl Cone {
+    /// Creates a new cone reaching `radius` cells from its origin, facing the angle and spreading
+    /// `half_angle` to either side of it.
+    #[inline]
+    pub fn new(origin: IVec2, facing: f32, half_angle: f32, radius: u32) -> Self {
+        Self {
+            origin,
+            facing: facing.rem_euclid(360.0),
+            half_angle: half_angle.clamp(0.0, 180.0),
+            radius,
+        }
+    }
+
+    /// Creates a new cone reaching `radius` cells from its origin, facing the direction and
+    /// spreading `half_angle` to either side of it.
+    #[inline]
+    pub fn from_direction(
+        origin: IVec2,
+        direction: Direction,
+        half_angle: f32,
+        radius: u32,
+    ) -> Self {
+        let coord = direction.coord().as_vec2();
+        Self::new(
+            origin,
+            coord.y.atan2(coord.x).to_degrees(),
+            half_angle,
+            radius,
+        )
+    }
+}
+
+impl Cone {
+    /// Get the origin of the cone
+    #[inline]
+    pub const fn origin(&self) -> IVec2 {
+        self.origin
+    }
+
+    /// Get the facing of the cone
+    #[inline]
+    pub const fn facing(&self) -> f32 {
+        self.facing
+    }
+
+    /// Get the angle the cone spreads to either side of its facing
+    #[inline]
+    pub const fn half_angle(&self) -> f32 {
+        self.half_angle
+    }
+
+    /// Get the radius of the cone
+    #[inline]
+    pub const fn radius(&self) -> u32 {
+        self.radius
+    }
+
+    /// Get the smallest rectangle containing the circle the cone is cut from
+    #[inline]
+    pub fn bounds(&self) -> Rectangle {
+        let radius = IVec2::splat(self.radius as i32);
+        Rectangle::new(self.origin - radius, self.origin + radius)
+    }
+}
+
+impl Shape for Cone {
+    #[inline]
+    fn get_count(&self) -> u32 {
+        self.iter().len() as u32
+    }
+
+    #[inline]
+    fn contains(&self, position: IVec2) -> bool {
+        let offset = position - self.origin;
+        if offset == IVec2::ZERO {
+            return true;
+        }
+
+        let (x, y) = (offset.x as i64, offset.y as i64);
+        let diameter = 2 * self.radius as i64 + 1;
+        if 4 * (x * x + y * y) >= diameter * diameter {
+            return false;
+        }
+
+        let angle = (offset.y as f64).atan2(offset.x as f64).to_degrees();
+        let difference = (angle - self.facing as f64 + 180.0).rem_euclid(360.0) - 180.0;
+        difference.abs() <= self.half_angle as f64 + f64::EPSILON.sqrt()
+    }
+
+    #[inline]
+    fn get_positions(&self) -> HashSet<IVec2> {
+        self.iter().collect()
+    }
+
+    #[inline]
+    fn boxed_iter(&self) -> BoxedShapeIter {
+        Box::new(self.into_iter())
+    }
+}
+
+impl ShapeWithBorder for Cone {
+    #[inline]
+    fn get_border_count(&self) -> usize {
+        border_points_within(self, self.bounds()).len()
+    }
+
+    #[inline]
+    fn border_contains(&self, point: IVec2) -> bool {
+        is_border_point(self, point)
+    }
+
+    #[inline]
+    fn get_border_positions(&self) -> HashSet<IVec2> {
+        border_points_within(self, self.bounds())
+    }
+}
+
+impl ShapeIter for Cone {
+    type Iterator = std::vec::IntoIter<IVec2>;
+
+    #[inline]
+    fn iter(&self) -> Self::Iterator {
+        self.into_iter()
+    }
+}
+
+impl IntoIterator for Cone {
+    type IntoIter = std::vec::IntoIter<IVec2>;
+    type Item = IVec2;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        points_within(&self, self.bounds())
+    }
+}
+
+impl From<Cone> for BoxedShape {
+    fn from(value: Cone) -> Self {
+        Box::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn breath() {
+        let cone = Cone::from_direction(IVec2::new(5, 5), Direction::EAST, 45.0, 4);
+        assert!(cone.contains(IVec2::new(5, 5)));
+        assert!(cone.contains(IVec2::new(9, 5)));
+        assert!(cone.contains(IVec2::new(7, 7)));
+        assert!(!cone.contains(IVec2::new(10, 5)));
+        assert!(!cone.contains(IVec2::new(6, 7)));
+        assert!(!cone.contains(IVec2::new(4, 5)));
+        assert_eq!(cone.get_count(), 1 + 3 + 5 + 7 + 7 - 2);
+
+        // the diagonal edges and the tip, around 9 cells along the facing
+        assert!(cone.border_contains(IVec2::new(5, 5)));
+        assert!(cone.border_contains(IVec2::new(7, 7)));
+        assert!(!cone.border_contains(IVec2::new(7, 5)));
+        assert_eq!(cone.get_border_count(), 21 - 9);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::prelude::*;
+
+use super::shape::{border_points_within, is_border_point, points_within};
+
+/// A grid based, axis aligned ellipse.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Ellipse {
+    center: IVec2,
+    radii: UVec2,
+}
+
+impl Ellipse {
+    /// Creates a new ellipse, reaching `radii.x` cells to the left and right of the center and
+    /// `radii.y` cells above and below it.
+    #[inline]
+    pub const fn new(center: IVec2, radii: UVec2) -> Self {
+        Self { center, radii }
+    }
+}
+
+impl Ellipse {
+    /// Get the center of the ellipse
+    #[inline]
+    pub const fn center(&self) -> IVec2 {
+        self.center
+    }
+
+    /// Get the horizontal and vertical radius of the ellipse
+    #[inline]
+    pub const fn radii(&self) -> UVec2 {
+        self.radii
+    }
+
+    /// Get the smallest rectangle containing the ellipse
+    #[inline]
+    pub fn bounds(&self) -> Rectangle {
+        let radii = self.radii.as_ivec2();
+        Rectangle::new(self.center - radii, self.center + radii)
+    }
+}
+
+impl Shape for Ellipse {
+    #[inline]
+    fn get_count(&self) -> u32 {
+        self.iter().len() as u32
+    }
+
+    // a cell is inside if its center is within the ellipse grown by half a cell
+    #[inline]
+    fn contains(&self, position: IVec2) -> bool {
+        let offset = position - self.center;
+        let (x, y) = (offset.x as i64, offset.y as i64);
+        let width = 2 * self.radii.x as i64 + 1;
+        let height = 2 * self.radii.y as i64 + 1;
+        4 * x * x * height * height + 4 * y * y * width * width < width * width * height * height
+    }
+
+    #[inline]
+    fn get_positions(&self) -> HashSet<IVec2> {
+        self.iter().collect()
+    }
+
+    #[inline]
+    fn boxed_iter(&self) -> BoxedShapeIter {
+        Box::new(self.into_iter())
+    }
+}
+
+impl ShapeWithBorder for Ellipse {
+    #[inline]
+    fn get_border_count(&self) -> usize {
+        border_points_within(self, self.bounds()).len()
+    }
+
+    #[inline]
+    fn border_contains(&self, point: IVec2) -> bool {
+        is_border_point(self, point)
+    }
+
+    #[inline]
+    fn get_border_positions(&self) -> HashSet<IVec2> {
+        border_points_within(self, self.bounds())
+    }
+}
+
+impl ShapeIter for Ellipse {
+    type Iterator = std::vec::IntoIter<IVec2>;
+
+    #[inline]
+    fn iter(&self) -> Self::Iterator {
+        self.into_iter()
+    }
+}
+
+impl IntoIterator for Ellipse {
+    type IntoIter = std::vec::IntoIter<IVec2>;
+    type Item = IVec2;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        points_within(&self, self.bounds())
+    }
+}
+
+impl From<Ellipse> for BoxedShape {
+    fn from(value: Ellipse) -> Self {
+        Box::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn wide() {
+        let ellipse = Ellipse::new(IVec2::new(5, 5), UVec2::new(3, 1));
+        assert!(ellipse.contains(IVec2::new(5, 5)));
+        assert!(ellipse.contains(IVec2::new(2, 5)));
+        assert!(ellipse.contains(IVec2::new(7, 4)));
+        assert!(!ellipse.contains(IVec2::new(8, 6)));
+        assert!(!ellipse.contains(IVec2::new(5, 7)));
+
+        // 7 cells across the middle, 5 on the rows above and below
+        assert_eq!(ellipse.get_count(), 17);
+        assert_eq!(Ellipse::new(IVec2::ZERO, UVec2::ZERO).get_count(), 1);
+
+        // all but the 5 middle cells of the widest row
+        assert!(ellipse.border_contains(IVec2::new(2, 5)));
+        assert!(ellipse.border_contains(IVec2::new(5, 4)));
+        assert!(!ellipse.border_contains(IVec2::new(5, 5)));
+        assert_eq!(ellipse.get_border_count(), 17 - 5);
+    }
+}
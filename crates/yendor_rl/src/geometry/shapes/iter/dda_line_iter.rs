@@ -0,0 +1,69 @@
+use crate::prelude::*;
+
+/// Line-drawing iterator using a digital differential analyzer, stepping one cell at a time
+/// along the longer axis of a line between arbitrary points.
+///
+/// Cell `(x, y)` covers the points within half a unit of its center at `(x, y)`, so sub-cell
+/// endpoints such as the muzzle of a gun or the tip of a wand can be used.
+#[derive(Debug, Clone)]
+pub struct DdaLineIter {
+    start: Vec2,
+    delta: Vec2,
+    first: IVec2,
+    axis: usize,
+    sign: i32,
+    index: u32,
+    steps: u32,
+}
+
+impl DdaLineIter {
+    /// Creates a new iterator. Yields the cells from the one containing `start` to the one
+    /// containing `end`, inclusively.
+    #[inline]
+    pub fn new(start: Vec2, end: Vec2) -> Self {
+        let first = start.round().as_ivec2();
+        let cells = end.round().as_ivec2() - first;
+        let axis = usize::from(cells.y.abs() > cells.x.abs());
+        Self {
+            start,
+            delta: end - start,
+            first,
+            axis,
+            sign: cells[axis].signum(),
+            index: 0,
+            steps: cells[axis].unsigned_abs(),
+        }
+    }
+}
+
+impl Iterator for DdaLineIter {
+    type Item = IVec2;
+
+    // walk the cells of the longer axis, sampling the line where it crosses their centers
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index > self.steps {
+            return None;
+        }
+
+        let major = self.first[self.axis] + self.sign * self.index as i32;
+        let t = if self.steps == 0 {
+            0.0
+        } else {
+            ((major as f32 - self.start[self.axis]) / self.delta[self.axis]).clamp(0.0, 1.0)
+        };
+        self.index += 1;
+
+        let mut cell = (self.start + self.delta * t).round().as_ivec2();
+        cell[self.axis] = major;
+        Some(cell)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.steps + 1 - self.index) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for DdaLineIter {}
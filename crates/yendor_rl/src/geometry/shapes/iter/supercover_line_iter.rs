@@ -0,0 +1,60 @@
+use crate::prelude::*;
+
+/// Line-drawing iterator yielding every cell the line between the centers of `start` and `end`
+/// passes through, moving diagonally where it crosses exactly through a corner.
+///
+/// Unlike [`BresenhamLineIter`], the line from `end` to `start` covers the same cells.
+#[derive(Debug, Clone)]
+pub struct SupercoverLineIter {
+    current: IVec2,
+    step: IVec2,
+    steps: IVec2,
+    taken: IVec2,
+    done: bool,
+}
+
+impl SupercoverLineIter {
+    /// Creates a new iterator. Yields points `start..=end`.
+    #[inline]
+    pub fn new(start: IVec2, end: IVec2) -> Self {
+        let delta = end - start;
+        Self {
+            current: start,
+            step: delta.signum(),
+            steps: delta.abs(),
+            taken: IVec2::ZERO,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for SupercoverLineIter {
+    type Item = IVec2;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let current = self.current;
+        if self.taken == self.steps {
+            self.done = true;
+            return Some(current);
+        }
+
+        // compare where the line crosses the next column against the next row
+        let decision =
+            (1 + 2 * self.taken.x) * self.steps.y - (1 + 2 * self.taken.y) * self.steps.x;
+        if decision <= 0 {
+            self.current.x += self.step.x;
+            self.taken.x += 1;
+        }
+        if decision >= 0 {
+            self.current.y += self.step.y;
+            self.taken.y += 1;
+        }
+
+        Some(current)
+    }
+}
@@ -0,0 +1,94 @@
+use crate::prelude::*;
+
+/// Line-drawing iterator yielding the same cells as Bresenham, except that the line from `end` to
+/// `start` covers the same cells as the line from `start` to `end`, in reverse.
+///
+/// Ties are always broken as if the line was drawn from the lesser endpoint, sorted by `x` then
+/// `y`.
+#[derive(Debug, Clone)]
+pub struct SymmetricLineIter {
+    origin: IVec2,
+    major: IVec2,
+    minor: IVec2,
+    major_delta: i32,
+    minor_delta: i32,
+    index: i32,
+    remaining: i32,
+    reversed: bool,
+}
+
+impl SymmetricLineIter {
+    /// Creates a new iterator. Yields points `start..=end`.
+    #[inline]
+    pub fn new(start: IVec2, end: IVec2) -> Self {
+        let reversed = (end.x, end.y) < (start.x, start.y);
+        let (origin, delta) = if reversed {
+            (end, start - end)
+        } else {
+            (start, end - start)
+        };
+
+        let (major, minor, major_delta, minor_delta) = if delta.x.abs() >= delta.y.abs() {
+            let major = IVec2::new(delta.x.signum(), 0);
+            (
+                major,
+                IVec2::new(0, delta.y.signum()),
+                delta.x.abs(),
+                delta.y.abs(),
+            )
+        } else {
+            let major = IVec2::new(0, delta.y.signum());
+            (
+                major,
+                IVec2::new(delta.x.signum(), 0),
+                delta.y.abs(),
+                delta.x.abs(),
+            )
+        };
+
+        Self {
+            origin,
+            major,
+            minor,
+            major_delta,
+            minor_delta,
+            index: if reversed { major_delta } else { 0 },
+            remaining: major_delta + 1,
+            reversed,
+        }
+    }
+
+    // the cell `index` steps along the major axis from the lesser endpoint, rounding half up
+    #[inline]
+    fn cell(&self, index: i32) -> IVec2 {
+        let minor = if self.major_delta == 0 {
+            0
+        } else {
+            (2 * index * self.minor_delta + self.major_delta) / (2 * self.major_delta)
+        };
+        self.origin + self.major * index + self.minor * minor
+    }
+}
+
+impl Iterator for SymmetricLineIter {
+    type Item = IVec2;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let cell = self.cell(self.index);
+        self.remaining -= 1;
+        self.index += if self.reversed { -1 } else { 1 };
+        Some(cell)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+}
+
+impl ExactSizeIterator for SymmetricLineIter {}
@@ -1,18 +1,51 @@
 use crate::prelude::*;
 
+/// The algorithm used to pick the cells of a [`Line`].
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum LineMode {
+    /// One cell per step along the longer axis, see [`BresenhamLineInclusiveIter`]. The line
+    /// from `end` to `start` may cover different cells.
+    #[default]
+    Bresenham,
+    /// Bresenham covering the same cells in both directions, see [`SymmetricLineIter`].
+    SymmetricBresenham,
+    /// Every cell the line passes through, see [`SupercoverLineIter`]. Symmetric.
+    Supercover,
+    /// One cell per step along the longer axis between the cell centers, see [`DdaLineIter`].
+    Dda,
+}
+
 /// A line.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Line {
     end: IVec2,
     start: IVec2,
+    mode: LineMode,
 }
 
 impl Line {
-    /// Creates a new line.
+    /// Creates a new line, drawn with [`LineMode::Bresenham`].
     #[inline(always)]
     pub const fn new(start: IVec2, end: IVec2) -> Self {
-        Self { start, end }
+        Self {
+            start,
+            end,
+            mode: LineMode::Bresenham,
+        }
+    }
+
+    /// Returns the line drawn with the mode.
+    #[inline]
+    pub const fn with_mode(self, mode: LineMode) -> Self {
+        Self { mode, ..self }
+    }
+
+    /// Returns the mode the line is drawn with.
+    #[inline]
+    pub const fn mode(&self) -> LineMode {
+        self.mode
     }
 
     #[allow(dead_code)]
@@ -25,7 +58,10 @@ impl Line {
 impl Shape for Line {
     #[inline]
     fn get_count(&self) -> u32 {
-        (self.end - self.start).abs().max_element() as u32
+        match self.mode {
+            LineMode::Supercover => self.iter().count() as u32,
+            _ => (self.end - self.start).abs().max_element() as u32 + 1,
+        }
     }
 
     #[inline]
@@ -45,7 +81,7 @@ impl Shape for Line {
 }
 
 impl ShapeIter for Line {
-    type Iterator = BresenhamLineInclusiveIter;
+    type Iterator = LineIter;
 
     #[inline]
     fn iter(&self) -> Self::Iterator {
@@ -54,18 +90,62 @@ impl ShapeIter for Line {
 }
 
 impl IntoIterator for Line {
-    type IntoIter = BresenhamLineInclusiveIter;
+    type IntoIter = LineIter;
     type Item = IVec2;
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
-        BresenhamLineInclusiveIter::new(self.start, self.end)
+        match self.mode {
+            LineMode::Bresenham => {
+                LineIter::Bresenham(BresenhamLineInclusiveIter::new(self.start, self.end))
+            }
+            LineMode::SymmetricBresenham => {
+                LineIter::SymmetricBresenham(SymmetricLineIter::new(self.start, self.end))
+            }
+            LineMode::Supercover => {
+                LineIter::Supercover(SupercoverLineIter::new(self.start, self.end))
+            }
+            LineMode::Dda => {
+                LineIter::Dda(DdaLineIter::new(self.start.as_vec2(), self.end.as_vec2()))
+            }
+        }
+    }
+}
+
+/// Iterator over the points of a [`Line`], drawn with its [`LineMode`].
+#[derive(Debug, Clone)]
+pub enum LineIter {
+    /// See [`LineMode::Bresenham`].
+    Bresenham(BresenhamLineInclusiveIter),
+    /// See [`LineMode::SymmetricBresenham`].
+    SymmetricBresenham(SymmetricLineIter),
+    /// See [`LineMode::Supercover`].
+    Supercover(SupercoverLineIter),
+    /// See [`LineMode::Dda`].
+    Dda(DdaLineIter),
+}
+
+impl Iterator for LineIter {
+    type Item = IVec2;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Bresenham(iter) => iter.next(),
+            Self::SymmetricBresenham(iter) => iter.next(),
+            Self::Supercover(iter) => iter.next(),
+            Self::Dda(iter) => iter.next(),
+        }
     }
 }
 
 impl Display for Line {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Line {{Start: {}, End: {}}}", self.start, self.end)
+        write!(
+            f,
+            "Line {{Start: {}, End: {}, Mode: {:?}}}",
+            self.start, self.end, self.mode
+        )
     }
 }
 
@@ -74,3 +154,110 @@ impl From<Line> for BoxedShape {
         Box::new(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn ends() -> impl Iterator<Item = (IVec2, IVec2)> {
+        let points = Rectangle::new(IVec2::splat(-4), IVec2::splat(4))
+            .into_iter()
+            .collect::<Vec<_>>();
+        points
+            .clone()
+            .into_iter()
+            .flat_map(move |start| points.clone().into_iter().map(move |end| (start, end)))
+    }
+
+    #[test]
+    fn modes() {
+        for (start, end) in ends() {
+            for mode in [
+                LineMode::Bresenham,
+                LineMode::SymmetricBresenham,
+                LineMode::Supercover,
+                LineMode::Dda,
+            ] {
+                let line = Line::new(start, end).with_mode(mode);
+                let points = line.iter().collect::<Vec<_>>();
+                assert_eq!(points.first(), Some(&start), "{line}");
+                assert_eq!(points.last(), Some(&end), "{line}");
+                assert_eq!(points.len() as u32, line.get_count(), "{line}");
+
+                // 8-connected, or 4-connected for supercover
+                for step in points.windows(2) {
+                    let delta = (step[1] - step[0]).abs();
+                    assert_eq!(delta.max_element(), 1, "{line}");
+                    if mode == LineMode::Supercover {
+                        assert!(delta.x + delta.y == 1 || delta.min_element() == 1, "{line}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn symmetric() {
+        for (start, end) in ends() {
+            for mode in [LineMode::SymmetricBresenham, LineMode::Supercover] {
+                let forward = Line::new(start, end)
+                    .with_mode(mode)
+                    .iter()
+                    .collect::<Vec<_>>();
+                let mut backward = Line::new(end, start)
+                    .with_mode(mode)
+                    .iter()
+                    .collect::<Vec<_>>();
+                backward.reverse();
+                assert_eq!(forward, backward, "{mode:?} {start} {end}");
+            }
+        }
+    }
+
+    #[test]
+    fn supercover() {
+        let line = Line::new(IVec2::ZERO, IVec2::new(2, 1)).with_mode(LineMode::Supercover);
+        assert_eq!(
+            line.iter().collect::<Vec<_>>(),
+            [
+                IVec2::new(0, 0),
+                IVec2::new(1, 0),
+                IVec2::new(1, 1),
+                IVec2::new(2, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn dda() {
+        let points = DdaLineIter::new(Vec2::new(0.3, 0.1), Vec2::new(2.7, 1.4)).collect::<Vec<_>>();
+        assert_eq!(
+            points,
+            [
+                IVec2::new(0, 0),
+                IVec2::new(1, 0),
+                IVec2::new(2, 1),
+                IVec2::new(3, 1)
+            ]
+        );
+
+        // the endpoints are rounded to their cells, so none is repeated
+        let points =
+            DdaLineIter::new(Vec2::new(0.3, 0.0), Vec2::new(2.35, 0.0)).collect::<Vec<_>>();
+        assert_eq!(
+            points,
+            [IVec2::new(0, 0), IVec2::new(1, 0), IVec2::new(2, 0)]
+        );
+        let points =
+            DdaLineIter::new(Vec2::new(2.35, 0.4), Vec2::new(0.3, 2.6)).collect::<Vec<_>>();
+        assert_eq!(
+            points,
+            [
+                IVec2::new(2, 0),
+                IVec2::new(2, 1),
+                IVec2::new(1, 2),
+                IVec2::new(0, 3)
+            ]
+        );
+    }
+}
@@ -3,16 +3,44 @@ mod iter {
     pub use rect_iter::*;
     mod line_iter;
     pub use line_iter::*;
+    mod supercover_line_iter;
+    pub use supercover_line_iter::*;
+    mod symmetric_line_iter;
+    pub use symmetric_line_iter::*;
+    mod dda_line_iter;
+    pub use dda_line_iter::*;
 }
 pub use iter::*;
 
+mod combinators {
+    mod difference;
+    pub use difference::*;
+    mod intersection;
+    pub use intersection::*;
+    mod translate;
+    pub use translate::*;
+    mod union;
+    pub use union::*;
+}
+pub use combinators::*;
+
 mod octant;
 pub use octant::*;
+mod annulus;
+pub use annulus::*;
 mod circle;
 pub use circle::*;
+mod cone;
+pub use cone::*;
+mod ellipse;
+pub use ellipse::*;
 mod line;
 pub use line::*;
+mod polygon;
+pub use polygon::*;
 mod rectangle;
 pub use rectangle::*;
 mod shape;
 pub use shape::*;
+mod triangle;
+pub use triangle::*;
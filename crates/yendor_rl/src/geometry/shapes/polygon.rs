@@ -0,0 +1,178 @@
+use crate::prelude::*;
+
+use super::shape::{border_points_within, is_border_point, points_within};
+
+/// A grid based polygon, convex or concave.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Polygon {
+    vertices: Vec<IVec2>,
+}
+
+impl Polygon {
+    /// Creates a new polygon from its corners, in any winding order. The last corner connects
+    /// back to the first.
+    #[inline]
+    pub fn new(vertices: impl IntoIterator<Item = IVec2>) -> Self {
+        Self {
+            vertices: vertices.into_iter().collect(),
+        }
+    }
+}
+
+impl Polygon {
+    /// Get the corners of the polygon
+    #[inline]
+    pub fn vertices(&self) -> &[IVec2] {
+        &self.vertices
+    }
+
+    /// Get the smallest rectangle containing the polygon
+    #[inline]
+    pub fn bounds(&self) -> Rectangle {
+        let min = self.vertices.iter().copied().reduce(IVec2::min);
+        let max = self.vertices.iter().copied().reduce(IVec2::max);
+        Rectangle::new(min.unwrap_or_default(), max.unwrap_or_default())
+    }
+
+    // every edge as `(start, end)`
+    #[inline]
+    fn edges(&self) -> impl Iterator<Item = (IVec2, IVec2)> + '_ {
+        self.vertices
+            .iter()
+            .copied()
+            .zip(self.vertices.iter().copied().cycle().skip(1))
+    }
+}
+
+impl Shape for Polygon {
+    #[inline]
+    fn get_count(&self) -> u32 {
+        self.iter().len() as u32
+    }
+
+    // a cell is inside if its center is inside or on an edge of the polygon, using the even-odd
+    // rule so self-intersecting polygons have holes
+    #[inline]
+    fn contains(&self, position: IVec2) -> bool {
+        let bounds = self.bounds();
+        if self.vertices.is_empty()
+            || position.cmplt(bounds.min).any()
+            || position.cmpgt(bounds.max).any()
+        {
+            return false;
+        }
+
+        let mut inside = false;
+        for (start, end) in self.edges() {
+            let (edge, offset) = (end - start, position - start);
+            let cross = edge.x as i64 * offset.y as i64 - edge.y as i64 * offset.x as i64;
+            if cross == 0
+                && position.cmpge(start.min(end)).all()
+                && position.cmple(start.max(end)).all()
+            {
+                return true;
+            }
+
+            // the edge crosses the ray from the cell towards +x
+            if (start.y > position.y) != (end.y > position.y) && (cross > 0) == (end.y > start.y) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    #[inline]
+    fn get_positions(&self) -> HashSet<IVec2> {
+        self.iter().collect()
+    }
+
+    #[inline]
+    fn boxed_iter(&self) -> BoxedShapeIter {
+        Box::new(self.clone().into_iter())
+    }
+}
+
+impl ShapeWithBorder for Polygon {
+    #[inline]
+    fn get_border_count(&self) -> usize {
+        border_points_within(self, self.bounds()).len()
+    }
+
+    #[inline]
+    fn border_contains(&self, point: IVec2) -> bool {
+        is_border_point(self, point)
+    }
+
+    #[inline]
+    fn get_border_positions(&self) -> HashSet<IVec2> {
+        border_points_within(self, self.bounds())
+    }
+}
+
+impl ShapeIter for Polygon {
+    type Iterator = std::vec::IntoIter<IVec2>;
+
+    #[inline]
+    fn iter(&self) -> Self::Iterator {
+        points_within(self, self.bounds())
+    }
+}
+
+impl IntoIterator for Polygon {
+    type IntoIter = std::vec::IntoIter<IVec2>;
+    type Item = IVec2;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        points_within(&self, self.bounds())
+    }
+}
+
+impl From<Polygon> for BoxedShape {
+    fn from(value: Polygon) -> Self {
+        Box::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn concave() {
+        // an L shaped room
+        let room = Polygon::new([
+            IVec2::new(0, 0),
+            IVec2::new(6, 0),
+            IVec2::new(6, 2),
+            IVec2::new(2, 2),
+            IVec2::new(2, 5),
+            IVec2::new(0, 5),
+        ]);
+        assert_eq!(room.get_count(), 7 * 3 + 3 * 3);
+        assert!(room.contains(IVec2::new(6, 2)));
+        assert!(room.contains(IVec2::new(1, 4)));
+        assert!(!room.contains(IVec2::new(4, 4)));
+        assert!(!room.contains(IVec2::new(7, 1)));
+
+        assert!(room.border_contains(IVec2::new(3, 2)));
+        assert!(!room.border_contains(IVec2::new(1, 1)));
+        assert_eq!(room.get_border_count(), 30 - 9);
+    }
+
+    #[test]
+    fn triangle() {
+        let (a, b, c) = (IVec2::new(-3, -2), IVec2::new(7, 1), IVec2::new(0, 6));
+        let triangle = Triangle::new(a, b, c);
+        assert_eq!(
+            triangle.get_positions(),
+            Polygon::new([a, b, c]).get_positions()
+        );
+        assert_eq!(
+            triangle.get_positions(),
+            Triangle::new(c, b, a).get_positions()
+        );
+        assert!(triangle.contains(a) && triangle.contains(b) && triangle.contains(c));
+    }
+}
@@ -1,5 +1,7 @@
 #![allow(dead_code)]
 
+use bevy::math::Rect;
+
 use crate::prelude::*;
 
 /// A 2D rectangle.
@@ -69,28 +71,33 @@ impl Rectangle {
 }
 
 impl Rectangle {
+    /// Get the center point of the rectangle.
     #[inline]
-    fn center(&self) -> IVec2 {
+    pub fn center(&self) -> IVec2 {
         self.min.mid_point(self.max)
     }
 
+    /// Get the left edge of the rectangle.
     #[inline]
-    fn left(&self) -> i32 {
+    pub fn left(&self) -> i32 {
         self.min.x.min(self.max.x)
     }
 
+    /// Get the right edge of the rectangle.
     #[inline]
-    fn right(&self) -> i32 {
+    pub fn right(&self) -> i32 {
         self.min.x.max(self.max.x)
     }
 
+    /// Get the top edge of the rectangle.
     #[inline]
-    fn top(&self) -> i32 {
+    pub fn top(&self) -> i32 {
         self.min.y.max(self.max.y)
     }
 
+    /// Get the bottom edge of the rectangle.
     #[inline]
-    fn bottom(&self) -> i32 {
+    pub fn bottom(&self) -> i32 {
         self.min.y.min(self.max.y)
     }
 
@@ -113,6 +120,130 @@ impl Rectangle {
     }
 }
 
+impl Rectangle {
+    /// Check if the point is inside the rectangle.
+    #[inline]
+    pub fn contains(&self, point: IVec2) -> bool {
+        point.cmpge(self.min).all() && point.cmple(self.max).all()
+    }
+
+    /// Check if the other rectangle lies entirely inside this rectangle.
+    #[inline]
+    pub fn contains_rect(&self, other: Self) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    /// Get the point inside the rectangle closest to the point.
+    #[inline]
+    pub fn clamp_point(&self, point: IVec2) -> IVec2 {
+        point.clamp(self.min, self.max)
+    }
+
+    /// Get the overlap of the two rectangles, if they intersect.
+    #[inline]
+    pub fn intersection(&self, other: Self) -> Option<Self> {
+        self.intersects(other).then(|| Self {
+            min: self.min.max(other.min),
+            max: self.max.min(other.max),
+        })
+    }
+
+    /// Get the smallest rectangle containing both rectangles.
+    #[inline]
+    pub fn union(&self, other: Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// Grow the rectangle by `margin.x` on the left and right and `margin.y` on the top and
+    /// bottom.
+    #[inline]
+    pub fn expand(&self, margin: UVec2) -> Self {
+        Self {
+            min: self.min - margin.as_ivec2(),
+            max: self.max + margin.as_ivec2(),
+        }
+    }
+
+    /// Shrink the rectangle by `margin.x` on the left and right and `margin.y` on the top and
+    /// bottom. A rectangle too small to shrink collapses onto its center.
+    #[inline]
+    pub fn shrink(&self, margin: UVec2) -> Self {
+        let min = self.min + margin.as_ivec2();
+        let max = self.max - margin.as_ivec2();
+        let center = self.center();
+        Self {
+            min: IVec2::select(min.cmple(max), min, center),
+            max: IVec2::select(min.cmple(max), max, center),
+        }
+    }
+
+    /// Split the rectangle with a horizontal cut into `(bottom, top)`, the bottom getting
+    /// `ratio` of the rows. Each half keeps at least one row.
+    ///
+    /// Returns `None` if the rectangle is a single row.
+    pub fn split_horizontal(&self, ratio: f32) -> Option<(Self, Self)> {
+        let rows = self.height() + 1;
+        if rows < 2 {
+            return None;
+        }
+
+        let split = self.min.y + Self::split_at(rows, ratio);
+        Some((
+            Self::new(self.min, IVec2::new(self.max.x, split - 1)),
+            Self::new(IVec2::new(self.min.x, split), self.max),
+        ))
+    }
+
+    /// Split the rectangle with a vertical cut into `(left, right)`, the left getting `ratio` of
+    /// the columns. Each half keeps at least one column.
+    ///
+    /// Returns `None` if the rectangle is a single column.
+    pub fn split_vertical(&self, ratio: f32) -> Option<(Self, Self)> {
+        let columns = self.width() + 1;
+        if columns < 2 {
+            return None;
+        }
+
+        let split = self.min.x + Self::split_at(columns, ratio);
+        Some((
+            Self::new(self.min, IVec2::new(split - 1, self.max.y)),
+            Self::new(IVec2::new(split, self.min.y), self.max),
+        ))
+    }
+
+    // the number of cells on the first side of a split
+    #[inline]
+    fn split_at(cells: i32, ratio: f32) -> i32 {
+        ((cells as f32 * ratio.clamp(0.0, 1.0)).round() as i32).clamp(1, cells - 1)
+    }
+}
+
+/// Each point covers the unit square it is the corner of, so the rectangle spans `min` to
+/// `max + 1`.
+impl From<Rectangle> for Rect {
+    fn from(value: Rectangle) -> Self {
+        Self {
+            min: value.min.as_vec2(),
+            max: (value.max + IVec2::ONE).as_vec2(),
+        }
+    }
+}
+
+/// The rectangle of every point whose unit square overlaps the rect.
+impl From<Rect> for Rectangle {
+    fn from(value: Rect) -> Self {
+        let min = value.min.min(value.max);
+        let max = value.min.max(value.max);
+        Self::new(
+            min.floor().as_ivec2(),
+            (max.ceil().as_ivec2() - IVec2::ONE).max(min.floor().as_ivec2()),
+        )
+    }
+}
+
 impl IntoIterator for Rectangle {
     type IntoIter = RectIter;
     type Item = IVec2;
@@ -122,3 +253,64 @@ impl IntoIterator for Rectangle {
         RectIter::new(self.min, self.max)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::Rect;
+
+    use crate::prelude::*;
+
+    #[test]
+    fn algebra() {
+        let a = Rectangle::new(IVec2::new(0, 0), IVec2::new(9, 5));
+        let b = Rectangle::new(IVec2::new(6, 3), IVec2::new(12, 12));
+        assert_eq!(
+            a.intersection(b),
+            Some(Rectangle::new(IVec2::new(6, 3), IVec2::new(9, 5)))
+        );
+        assert_eq!(
+            a.intersection(b.expand(UVec2::new(0, 10)).shrink(UVec2::new(3, 0)))
+                .map(|r| r.width()),
+            Some(0)
+        );
+        assert_eq!(
+            a.intersection(Rectangle::new(IVec2::new(10, 0), IVec2::new(12, 5))),
+            None
+        );
+        assert_eq!(a.union(b), Rectangle::new(IVec2::ZERO, IVec2::splat(12)));
+        assert!(a.union(b).contains_rect(a) && !a.contains_rect(b));
+
+        assert_eq!(
+            a.shrink(UVec2::ONE),
+            Rectangle::new(IVec2::ONE, IVec2::new(8, 4))
+        );
+        assert_eq!(a.shrink(UVec2::ONE).expand(UVec2::ONE), a);
+        assert_eq!(a.shrink(UVec2::splat(4)).height(), 0);
+        assert_eq!(a.clamp_point(IVec2::new(-3, 4)), IVec2::new(0, 4));
+    }
+
+    #[test]
+    fn split() {
+        let room = Rectangle::new(IVec2::new(2, 2), IVec2::new(11, 5));
+        let (left, right) = room.split_vertical(0.3).unwrap();
+        assert_eq!(left, Rectangle::new(IVec2::new(2, 2), IVec2::new(4, 5)));
+        assert_eq!(right, Rectangle::new(IVec2::new(5, 2), IVec2::new(11, 5)));
+
+        let (bottom, top) = room.split_horizontal(0.0).unwrap();
+        assert_eq!(bottom.height(), 0);
+        assert_eq!(top, Rectangle::new(IVec2::new(2, 3), IVec2::new(11, 5)));
+        assert_eq!(bottom.split_horizontal(0.5), None);
+    }
+
+    #[test]
+    fn bevy_rect() {
+        let room = Rectangle::new(IVec2::new(-2, 1), IVec2::new(3, 4));
+        let rect = Rect::from(room);
+        assert_eq!(rect, Rect::new(-2.0, 1.0, 4.0, 5.0));
+        assert_eq!(Rectangle::from(rect), room);
+        assert_eq!(
+            Rectangle::from(Rect::new(0.5, 0.5, 1.5, 0.7)),
+            Rectangle::new(IVec2::ZERO, IVec2::new(1, 0))
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use crate::prelude::*;
 
 /// Boxed shape
-pub type BoxedShape = Box<dyn Shape>;
+pub type BoxedShape = Box<dyn Shape + Send + Sync>;
 
 /// Boxed shape iterator
 pub type BoxedShapeIter = Box<dyn Iterator<Item = IVec2>>;
@@ -41,3 +41,55 @@ pub trait ShapeWithBorder: Shape {
     /// returns an iterator over all of the points
     fn get_border_positions(&self) -> HashSet<IVec2>;
 }
+
+/// Collects the points within `bounds` contained by `shape`, for shapes without a cheaper way
+/// to walk their points.
+pub(super) fn points_within(shape: &impl Shape, bounds: Rectangle) -> std::vec::IntoIter<IVec2> {
+    bounds
+        .into_iter()
+        .filter(|position| shape.contains(*position))
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+/// Returns `true` if the point is inside `shape` next to a cardinal neighbor outside of it.
+pub(super) fn is_border_point(shape: &impl Shape, point: IVec2) -> bool {
+    shape.contains(point)
+        && CardinalDirection::all().any(|direction| !shape.contains(point + direction.coord()))
+}
+
+/// Collects the points within `bounds` on the border of `shape`.
+pub(super) fn border_points_within(shape: &impl Shape, bounds: Rectangle) -> HashSet<IVec2> {
+    points_within(shape, bounds)
+        .filter(|point| is_border_point(shape, *point))
+        .collect()
+}
+
+/// A set of positions, such as a field of view, as a shape.
+impl Shape for HashSet<IVec2> {
+    #[inline]
+    fn get_count(&self) -> u32 {
+        self.len() as u32
+    }
+
+    #[inline]
+    fn contains(&self, point: IVec2) -> bool {
+        Self::contains(self, &point)
+    }
+
+    #[inline]
+    fn get_positions(&self) -> HashSet<IVec2> {
+        self.clone()
+    }
+
+    #[inline]
+    fn boxed_iter(&self) -> BoxedShapeIter {
+        Box::new(self.clone().into_iter())
+    }
+}
+
+impl From<HashSet<IVec2>> for BoxedShape {
+    fn from(value: HashSet<IVec2>) -> Self {
+        Box::new(value)
+    }
+}
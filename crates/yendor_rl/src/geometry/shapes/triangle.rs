@@ -0,0 +1,136 @@
+use crate::prelude::*;
+
+use super::shape::{border_points_within, is_border_point, points_within};
+
+/// A grid based triangle.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Triangle {
+    vertices: [IVec2; 3],
+}
+
+impl Triangle {
+    /// Creates a new triangle, in any winding order.
+    #[inline]
+    pub const fn new(a: IVec2, b: IVec2, c: IVec2) -> Self {
+        Self {
+            vertices: [a, b, c],
+        }
+    }
+}
+
+impl Triangle {
+    /// Get the corners of the triangle
+    #[inline]
+    pub const fn vertices(&self) -> [IVec2; 3] {
+        self.vertices
+    }
+
+    /// Get the smallest rectangle containing the triangle
+    #[inline]
+    pub fn bounds(&self) -> Rectangle {
+        let [a, b, c] = self.vertices;
+        Rectangle::new(a.min(b).min(c), a.max(b).max(c))
+    }
+}
+
+impl Shape for Triangle {
+    #[inline]
+    fn get_count(&self) -> u32 {
+        self.iter().len() as u32
+    }
+
+    // a cell is inside if its center is inside or on an edge of the triangle
+    #[inline]
+    fn contains(&self, position: IVec2) -> bool {
+        let [a, b, c] = self.vertices;
+        let bounds = self.bounds();
+        if position.cmplt(bounds.min).any() || position.cmpgt(bounds.max).any() {
+            return false;
+        }
+
+        let sides = [(a, b), (b, c), (c, a)].map(|(start, end)| {
+            let (edge, offset) = (end - start, position - start);
+            (edge.x as i64 * offset.y as i64 - edge.y as i64 * offset.x as i64).signum()
+        });
+        !(sides.contains(&1) && sides.contains(&-1))
+    }
+
+    #[inline]
+    fn get_positions(&self) -> HashSet<IVec2> {
+        self.iter().collect()
+    }
+
+    #[inline]
+    fn boxed_iter(&self) -> BoxedShapeIter {
+        Box::new(self.into_iter())
+    }
+}
+
+impl ShapeWithBorder for Triangle {
+    #[inline]
+    fn get_border_count(&self) -> usize {
+        border_points_within(self, self.bounds()).len()
+    }
+
+    #[inline]
+    fn border_contains(&self, point: IVec2) -> bool {
+        is_border_point(self, point)
+    }
+
+    #[inline]
+    fn get_border_positions(&self) -> HashSet<IVec2> {
+        border_points_within(self, self.bounds())
+    }
+}
+
+impl ShapeIter for Triangle {
+    type Iterator = std::vec::IntoIter<IVec2>;
+
+    #[inline]
+    fn iter(&self) -> Self::Iterator {
+        self.into_iter()
+    }
+}
+
+impl IntoIterator for Triangle {
+    type IntoIter = std::vec::IntoIter<IVec2>;
+    type Item = IVec2;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        points_within(&self, self.bounds())
+    }
+}
+
+impl From<Triangle> for BoxedShape {
+    fn from(value: Triangle) -> Self {
+        Box::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn right_angle() {
+        let (a, b, c) = (IVec2::ZERO, IVec2::new(4, 0), IVec2::new(0, 4));
+        let triangle = Triangle::new(a, b, c);
+        assert!(triangle.contains(IVec2::new(1, 1)));
+        assert!(triangle.contains(IVec2::new(2, 2)));
+        assert!(triangle.contains(c));
+        assert!(!triangle.contains(IVec2::new(3, 2)));
+        assert!(!triangle.contains(IVec2::new(-1, 0)));
+
+        // 5 + 4 + 3 + 2 + 1 cells, whatever the winding
+        assert_eq!(triangle.get_count(), 15);
+        assert_eq!(Triangle::new(c, b, a).get_count(), 15);
+
+        // only (1, 1), (2, 1) and (1, 2) are surrounded
+        assert!(triangle.border_contains(IVec2::new(2, 2)));
+        assert!(triangle.border_contains(a));
+        assert!(!triangle.border_contains(IVec2::new(1, 1)));
+        assert_eq!(triangle.get_border_count(), 15 - 3);
+    }
+}
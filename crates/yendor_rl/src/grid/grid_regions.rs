@@ -0,0 +1,259 @@
+use crate::prelude::*;
+
+/// Which neighbours of a cell are connected to it.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum Connectivity {
+    /// The 4 neighbours sharing a side, see [`CardinalDirection`].
+    #[default]
+    Cardinal,
+    /// All 8 neighbours, including the diagonals.
+    Moore,
+}
+
+impl Connectivity {
+    /// Returns an iterator over the directions of the connected neighbours.
+    #[inline]
+    pub const fn directions(self) -> DirectionIter {
+        match self {
+            Self::Cardinal => DirectionIter::cardinal(),
+            Self::Moore => DirectionIter::all_2d(),
+        }
+    }
+}
+
+/// A group of connected cells found by [`GridRegions::label_regions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    /// The label of the cells in the region, starting at `1`.
+    pub id: u32,
+    /// The number of cells in the region.
+    pub size: u32,
+    /// The smallest rectangle containing the region.
+    pub bounds: Rectangle,
+}
+
+/// The connected regions of a grid, found by [`GridRegions::label_regions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regions {
+    labels: Grid<u32>,
+    regions: Vec<Region>,
+}
+
+impl Regions {
+    /// Returns the id of the region every cell belongs to, `0` for cells in no region.
+    #[inline]
+    pub const fn labels(&self) -> &Grid<u32> {
+        &self.labels
+    }
+
+    /// Returns every region, ordered by id.
+    #[inline]
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    /// Returns the region with the id.
+    #[inline]
+    pub fn region(&self, id: u32) -> Option<&Region> {
+        self.regions.get((id as usize).checked_sub(1)?)
+    }
+
+    /// Returns the region the position belongs to.
+    #[inline]
+    pub fn region_at(&self, position: IVec2) -> Option<&Region> {
+        if position.is_valid(self.labels.dimensions()) {
+            self.region(self.labels[position])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the region with the most cells, the first one found on a tie.
+    #[inline]
+    pub fn largest_region(&self) -> Option<&Region> {
+        self.regions.iter().rev().max_by_key(|region| region.size)
+    }
+
+    /// Returns the cells of the region with the id.
+    pub fn mask(&self, id: u32) -> BitGrid {
+        let mut mask = BitGrid::new_default(self.labels.dimensions());
+        if id != 0 {
+            for (index, label) in self.labels.iter().enumerate() {
+                if *label == id {
+                    mask.cells.set(index, true);
+                }
+            }
+        }
+        mask
+    }
+}
+
+/// Flood fill and connected region analysis, for map generators and reachability checks.
+///
+/// ```ignore
+/// let reachable = map.flood_fill(stairs, |tile| tile.is_walkable(), Connectivity::Moore);
+/// let regions = map.label_regions(|tile| tile.is_walkable(), Connectivity::Cardinal);
+/// let fully_connected = regions.regions().len() == 1;
+/// ```
+pub trait GridRegions<T> {
+    /// Returns the cells matching the predicate which can be reached from `start` through cells
+    /// matching the predicate. The mask is empty if `start` does not match.
+    fn flood_fill(
+        &self,
+        start: IVec2,
+        predicate: impl FnMut(&T) -> bool,
+        connectivity: Connectivity,
+    ) -> BitGrid;
+
+    /// Groups the cells matching the predicate into regions of connected cells.
+    fn label_regions(
+        &self,
+        predicate: impl FnMut(&T) -> bool,
+        connectivity: Connectivity,
+    ) -> Regions;
+}
+
+impl<T, G: GridLayer<T>> GridRegions<T> for G {
+    fn flood_fill(
+        &self,
+        start: IVec2,
+        mut predicate: impl FnMut(&T) -> bool,
+        connectivity: Connectivity,
+    ) -> BitGrid {
+        let dimensions = self.dimensions();
+        let mut filled = BitGrid::new_default(dimensions);
+        let mut is_open = |position: IVec2| {
+            position.is_valid(dimensions) && predicate(self.get_unchecked(position.as_uvec2()))
+        };
+        if !is_open(start) {
+            return filled;
+        }
+
+        filled.set_unchecked(start.as_uvec2(), true);
+        let mut open = vec![start];
+        while let Some(position) = open.pop() {
+            for direction in connectivity.directions() {
+                let neighbour = position + direction.coord();
+                if is_open(neighbour) && !filled[neighbour] {
+                    filled.set_unchecked(neighbour.as_uvec2(), true);
+                    open.push(neighbour);
+                }
+            }
+        }
+        filled
+    }
+
+    fn label_regions(
+        &self,
+        mut predicate: impl FnMut(&T) -> bool,
+        connectivity: Connectivity,
+    ) -> Regions {
+        let dimensions = self.dimensions();
+        let mut labels = Grid::new_copy(dimensions, 0_u32);
+        let mut matches = BitGrid::new_default(dimensions);
+        for position in matches.point_iter() {
+            let cell = self.get_unchecked(position.as_uvec2());
+            matches.set_unchecked(position.as_uvec2(), predicate(cell));
+        }
+
+        let mut regions = Vec::new();
+        let mut open = Vec::new();
+        for start in matches.point_iter() {
+            if !matches[start] || labels[start] != 0 {
+                continue;
+            }
+
+            let id = regions.len() as u32 + 1;
+            let mut region = Region {
+                id,
+                size: 0,
+                bounds: Rectangle::new(start, start),
+            };
+
+            labels[start] = id;
+            open.push(start);
+            while let Some(position) = open.pop() {
+                region.size += 1;
+                region.bounds = region.bounds.union(Rectangle::new(position, position));
+                for direction in connectivity.directions() {
+                    let neighbour = position + direction.coord();
+                    if neighbour.is_valid(dimensions)
+                        && matches[neighbour]
+                        && labels[neighbour] == 0
+                    {
+                        labels[neighbour] = id;
+                        open.push(neighbour);
+                    }
+                }
+            }
+            regions.push(region);
+        }
+
+        Regions { labels, regions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn map() -> Grid<char> {
+        let rows = [
+            "....#...", //
+            "...#..#.", //
+            "####..#.", //
+            "..#.###.", //
+            "..#.....", //
+        ];
+        let mut map = Grid::new_copy(UVec2::new(8, 5), '#');
+        for (y, row) in rows.iter().enumerate() {
+            for (x, cell) in row.chars().enumerate() {
+                map[IVec2::new(x as i32, y as i32)] = cell;
+            }
+        }
+        map
+    }
+
+    #[test]
+    fn flood_fill() {
+        let map = map();
+        let open = |cell: &char| *cell == '.';
+
+        let cardinal = map.flood_fill(IVec2::ZERO, open, Connectivity::Cardinal);
+        assert_eq!(cardinal.iter().filter(|cell| **cell).count(), 7);
+        assert!(!cardinal[IVec2::new(4, 1)]);
+
+        let moore = map.flood_fill(IVec2::ZERO, open, Connectivity::Moore);
+        assert!(moore[IVec2::new(4, 1)]);
+        assert!(moore[IVec2::new(3, 4)]);
+        assert!(!moore[IVec2::new(0, 4)]);
+
+        let wall = map.flood_fill(IVec2::new(4, 0), open, Connectivity::Moore);
+        assert!(wall.iter().all(|cell| !*cell));
+    }
+
+    #[test]
+    fn label_regions() {
+        let map = map();
+        let regions = map.label_regions(|cell| *cell == '.', Connectivity::Cardinal);
+        assert_eq!(regions.regions().len(), 3);
+
+        let largest = regions.largest_region().unwrap();
+        assert_eq!(largest.size, 16);
+        assert_eq!(
+            largest.bounds,
+            Rectangle::new(IVec2::new(3, 0), IVec2::new(7, 4))
+        );
+        assert_eq!(regions.region_at(IVec2::new(7, 4)), Some(largest));
+        assert_eq!(regions.region_at(IVec2::new(0, 4)).unwrap().size, 4);
+        assert_eq!(regions.region_at(IVec2::new(2, 4)), None);
+        assert_eq!(regions.labels()[IVec2::new(2, 4)], 0);
+
+        let mask = regions.mask(largest.id);
+        assert_eq!(mask.iter().filter(|cell| **cell).count(), 16);
+
+        let moore = map.label_regions(|cell| *cell == '.', Connectivity::Moore);
+        assert_eq!(moore.regions().len(), 2);
+    }
+}
@@ -5,3 +5,7 @@ mod grid_iterable;
 pub use grid_iterable::*;
 mod grid_layer;
 pub use grid_layer::*;
+#[cfg(feature = "bitgrid")]
+mod grid_regions;
+#[cfg(feature = "bitgrid")]
+pub use grid_regions::*;
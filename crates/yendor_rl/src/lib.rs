@@ -9,6 +9,12 @@ pub mod distance;
 pub mod fov;
 pub mod geometry;
 pub mod grid;
+#[cfg(feature = "lighting")]
+pub mod lighting;
+#[cfg(feature = "fov")]
+pub mod los;
+#[cfg(feature = "mapgen")]
+pub mod mapgen;
 #[cfg(feature = "pathfinding")]
 pub mod pathfinding;
 #[cfg(feature = "random")]
@@ -54,6 +60,12 @@ pub mod prelude {
 
     #[cfg(feature = "fov")]
     pub use crate::fov::*;
+    #[cfg(feature = "lighting")]
+    pub use crate::lighting::*;
+    #[cfg(feature = "fov")]
+    pub use crate::los::*;
+    #[cfg(feature = "mapgen")]
+    pub use crate::mapgen::*;
     #[cfg(feature = "pathfinding")]
     pub use crate::pathfinding::*;
     #[cfg(feature = "random")]
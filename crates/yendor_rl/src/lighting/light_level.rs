@@ -0,0 +1,84 @@
+use crate::prelude::*;
+
+/// The amount of red, green and blue light on a cell.
+///
+/// Levels add up as lights overlap, a channel of `1.0` is fully lit but brighter levels are kept
+/// so overlapping lights can be told apart.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct LightLevel {
+    /// The red channel.
+    pub red: f32,
+    /// The green channel.
+    pub green: f32,
+    /// The blue channel.
+    pub blue: f32,
+}
+
+impl LightLevel {
+    /// No light at all.
+    pub const BLACK: Self = Self::new(0.0, 0.0, 0.0);
+    /// Full white light.
+    pub const WHITE: Self = Self::new(1.0, 1.0, 1.0);
+
+    /// Creates a new light level.
+    pub const fn new(red: f32, green: f32, blue: f32) -> Self {
+        Self { red, green, blue }
+    }
+
+    /// Returns the brightest channel.
+    #[inline]
+    pub const fn brightness(&self) -> f32 {
+        self.red.max(self.green).max(self.blue)
+    }
+
+    /// Returns true if every channel is dark.
+    #[inline]
+    pub fn is_dark(&self) -> bool {
+        self.brightness() <= 0.0
+    }
+}
+
+impl Add for LightLevel {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.red + rhs.red,
+            self.green + rhs.green,
+            self.blue + rhs.blue,
+        )
+    }
+}
+
+impl AddAssign for LightLevel {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+/// Channels never go below zero.
+impl Sub for LightLevel {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(
+            (self.red - rhs.red).max(0.0),
+            (self.green - rhs.green).max(0.0),
+            (self.blue - rhs.blue).max(0.0),
+        )
+    }
+}
+
+impl SubAssign for LightLevel {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<f32> for LightLevel {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self::new(self.red * rhs, self.green * rhs, self.blue * rhs)
+    }
+}
@@ -0,0 +1,259 @@
+use crate::prelude::*;
+
+/// The handle of a light added to a [`LightMap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LightId(usize);
+
+// a light along with the light it last cast on each cell
+struct Light {
+    source: LightSource,
+    contributions: Vec<(IVec2, LightLevel)>,
+}
+
+/// The light level on every cell of a map, accumulated from any number of [`LightSource`]s.
+///
+/// Each light remembers the cells it lit, so moving or removing a light only touches the cells
+/// around it instead of relighting the whole map.
+///
+/// ```ignore
+/// let mut lighting = LightMap::new(map.dimensions(), Fov::Shadowcast);
+/// let torch = lighting.add_light(LightSource::new(position, 8, 1.0), &mut provider, ());
+/// lighting.move_light(torch, position + IVec2::X, &mut provider, ());
+/// let level = lighting.get(player_position);
+/// ```
+pub struct LightMap {
+    fov: Fov,
+    levels: Grid<LightLevel>,
+    lights: Vec<Option<Light>>,
+}
+
+impl LightMap {
+    /// Creates a new, dark map using the field of view algorithm to find the cells each light
+    /// reaches.
+    pub fn new(dimensions: UVec2, fov: Fov) -> Self {
+        Self {
+            fov,
+            levels: Grid::new_copy(dimensions, LightLevel::BLACK),
+            lights: Vec::new(),
+        }
+    }
+
+    /// Returns the dimensions of the map.
+    #[inline]
+    pub fn dimensions(&self) -> UVec2 {
+        self.levels.dimensions()
+    }
+
+    /// Returns the light level on every cell.
+    #[inline]
+    pub const fn levels(&self) -> &Grid<LightLevel> {
+        &self.levels
+    }
+
+    /// Returns the light level on the position, positions outside the map are dark.
+    #[inline]
+    pub fn get(&self, position: IVec2) -> LightLevel {
+        if position.is_valid(self.dimensions()) {
+            self.levels[position]
+        } else {
+            LightLevel::BLACK
+        }
+    }
+
+    /// Returns the light with the id, if it hasn't been removed.
+    #[inline]
+    pub fn light(&self, id: LightId) -> Option<&LightSource> {
+        self.lights
+            .get(id.0)
+            .and_then(Option::as_ref)
+            .map(|light| &light.source)
+    }
+
+    /// Returns every light on the map.
+    pub fn lights(&self) -> impl Iterator<Item = (LightId, &LightSource)> {
+        self.lights
+            .iter()
+            .enumerate()
+            .filter_map(|(index, light)| Some((LightId(index), &light.as_ref()?.source)))
+    }
+
+    /// Adds a light, lighting the cells it can see.
+    pub fn add_light<T>(
+        &mut self,
+        source: LightSource,
+        provider: &mut impl FovProvider<T>,
+        pass_through_data: T,
+    ) -> LightId {
+        let light = self.cast(source, provider, pass_through_data);
+
+        if let Some(index) = self.lights.iter().position(Option::is_none) {
+            self.lights[index] = Some(light);
+            LightId(index)
+        } else {
+            self.lights.push(Some(light));
+            LightId(self.lights.len() - 1)
+        }
+    }
+
+    /// Removes a light, darkening the cells it lit.
+    pub fn remove_light(&mut self, id: LightId) -> Option<LightSource> {
+        let light = self.lights.get_mut(id.0)?.take()?;
+        self.uncast(&light);
+        Some(light.source)
+    }
+
+    /// Replaces a light, relighting only the cells around the old and new light.
+    ///
+    /// Returns `false` if the light has been removed.
+    pub fn update_light<T>(
+        &mut self,
+        id: LightId,
+        source: LightSource,
+        provider: &mut impl FovProvider<T>,
+        pass_through_data: T,
+    ) -> bool {
+        let Some(old) = self.lights.get_mut(id.0).and_then(Option::take) else {
+            return false;
+        };
+
+        self.uncast(&old);
+        self.lights[id.0] = Some(self.cast(source, provider, pass_through_data));
+        true
+    }
+
+    /// Moves a light, relighting only the cells around its old and new position.
+    ///
+    /// Returns `false` if the light has been removed.
+    pub fn move_light<T>(
+        &mut self,
+        id: LightId,
+        position: IVec2,
+        provider: &mut impl FovProvider<T>,
+        pass_through_data: T,
+    ) -> bool {
+        let Some(mut source) = self.light(id).copied() else {
+            return false;
+        };
+
+        source.position = position;
+        self.update_light(id, source, provider, pass_through_data)
+    }
+
+    /// Relights the whole map, needed after the cells blocking light have changed.
+    pub fn relight<T: Clone>(&mut self, provider: &mut impl FovProvider<T>, pass_through_data: T) {
+        self.levels
+            .iter_mut()
+            .for_each(|level| *level = LightLevel::BLACK);
+
+        for index in 0..self.lights.len() {
+            if let Some(light) = self.lights[index].take() {
+                let light = self.cast(light.source, provider, pass_through_data.clone());
+                self.lights[index] = Some(light);
+            }
+        }
+    }
+
+    // adds the light the source casts on every cell it can see
+    fn cast<T>(
+        &mut self,
+        source: LightSource,
+        provider: &mut impl FovProvider<T>,
+        pass_through_data: T,
+    ) -> Light {
+        let dimensions = self.dimensions();
+        let contributions = self
+            .fov
+            .compute(source.position, source.radius, provider, pass_through_data)
+            .into_iter()
+            .filter(|position| position.is_valid(dimensions))
+            .map(|position| (position, source.light_at(position)))
+            .filter(|(_, level)| !level.is_dark())
+            .collect::<Vec<_>>();
+
+        for (position, level) in &contributions {
+            self.levels[*position] += *level;
+        }
+
+        Light {
+            source,
+            contributions,
+        }
+    }
+
+    fn uncast(&mut self, light: &Light) {
+        for (position, level) in &light.contributions {
+            self.levels[*position] -= *level;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    // a 16x8 room split by a wall with a gap at the bottom
+    fn walls() -> Grid<bool> {
+        let mut walls = Grid::new_copy(UVec2::new(16, 8), false);
+        for y in 1..8 {
+            walls[IVec2::new(8, y)] = true;
+        }
+        walls
+    }
+
+    fn assert_same_light(a: &LightMap, b: &LightMap) {
+        for (a, b) in a.levels().iter().zip(b.levels().iter()) {
+            assert!((a.red - b.red).abs() < 1e-4, "{a:?} {b:?}");
+            assert!((a.green - b.green).abs() < 1e-4, "{a:?} {b:?}");
+            assert!((a.blue - b.blue).abs() < 1e-4, "{a:?} {b:?}");
+        }
+    }
+
+    #[test]
+    fn occlusion() {
+        let walls = walls();
+        let mut provider = GridFovProvider::new(&walls, |wall: &bool| *wall);
+        let mut lighting = LightMap::new(walls.dimensions(), Fov::Shadowcast);
+        let torch =
+            LightSource::new(IVec2::new(4, 4), 10, 1.0).with_color(LightLevel::new(1.0, 0.5, 0.0));
+        lighting.add_light(torch, &mut provider, ());
+
+        let level = lighting.get(IVec2::new(4, 4));
+        assert_eq!(level, LightLevel::new(1.0, 0.5, 0.0));
+        assert!(lighting.get(IVec2::new(6, 4)).brightness() < level.brightness());
+        assert!(!lighting.get(IVec2::new(8, 4)).is_dark());
+        assert!(lighting.get(IVec2::new(10, 4)).is_dark());
+        assert!(lighting.get(IVec2::new(-1, 4)).is_dark());
+    }
+
+    #[test]
+    fn incremental() {
+        let walls = walls();
+        let mut provider = GridFovProvider::new(&walls, |wall: &bool| *wall);
+        let torch = LightSource::new(IVec2::new(2, 2), 6, 1.0);
+        let fungus = LightSource::new(IVec2::new(12, 5), 3, 0.5)
+            .with_color(LightLevel::new(0.2, 1.0, 0.4))
+            .with_falloff(Distance::PythagorasSquared);
+
+        let mut lighting = LightMap::new(walls.dimensions(), Fov::Adams);
+        let torch_id = lighting.add_light(torch, &mut provider, ());
+        let fungus_id = lighting.add_light(fungus, &mut provider, ());
+        for x in 3..12 {
+            lighting.move_light(torch_id, IVec2::new(x, 0), &mut provider, ());
+        }
+
+        let mut expected = LightMap::new(walls.dimensions(), Fov::Adams);
+        expected.add_light(fungus, &mut provider, ());
+        expected.add_light(
+            LightSource::new(IVec2::new(11, 0), 6, 1.0),
+            &mut provider,
+            (),
+        );
+        assert_same_light(&lighting, &expected);
+
+        assert_eq!(lighting.remove_light(fungus_id), Some(fungus));
+        assert_eq!(lighting.remove_light(fungus_id), None);
+        lighting.remove_light(torch_id);
+        assert!(lighting.lights().next().is_none());
+        assert_same_light(&lighting, &LightMap::new(walls.dimensions(), Fov::Adams));
+    }
+}
@@ -0,0 +1,57 @@
+use crate::prelude::*;
+
+/// A light shining from a position, such as a torch or a glowing fungus.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct LightSource {
+    /// The position of the light.
+    pub position: IVec2,
+    /// The furthest distance the light reaches.
+    pub radius: u32,
+    /// The brightness of the light at its position.
+    pub intensity: f32,
+    /// The color of the light.
+    pub color: LightLevel,
+    /// How distance is measured when fading the light out towards its radius.
+    pub falloff: Distance,
+}
+
+impl LightSource {
+    /// Creates a new white light, fading out with [`Distance::Pythagoras`].
+    pub const fn new(position: IVec2, radius: u32, intensity: f32) -> Self {
+        Self {
+            position,
+            radius,
+            intensity,
+            color: LightLevel::WHITE,
+            falloff: Distance::Pythagoras,
+        }
+    }
+
+    /// Returns a copy of this light with the given color.
+    pub const fn with_color(mut self, color: LightLevel) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Returns a copy of this light fading out with the given distance.
+    pub const fn with_falloff(mut self, falloff: Distance) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    /// Returns the light this source casts on the position, ignoring anything in the way.
+    ///
+    /// The light fades out linearly with distance, reaching darkness just beyond the radius.
+    /// With [`Distance::PythagorasSquared`] it fades out quadratically instead.
+    pub fn light_at(&self, position: IVec2) -> LightLevel {
+        let reach = (self.radius + 1) as f32;
+        let reach = match self.falloff {
+            Distance::PythagorasSquared => reach * reach,
+            _ => reach,
+        };
+
+        let distance = self.falloff.distance2d(self.position, position);
+        let attenuation = (1.0 - distance / reach).clamp(0.0, 1.0);
+        self.color * (self.intensity * attenuation)
+    }
+}
@@ -0,0 +1,7 @@
+//! Provides colored lighting, using field of view to find the cells each light reaches.
+mod light_level;
+pub use light_level::*;
+mod light_map;
+pub use light_map::*;
+mod light_source;
+pub use light_source::*;
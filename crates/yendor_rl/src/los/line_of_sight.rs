@@ -0,0 +1,160 @@
+use crate::prelude::*;
+
+/// Checks whether one cell can see or shoot another by tracing a single line between them,
+/// far cheaper than computing a field of view for ranged attacks and projectiles.
+///
+/// ```ignore
+/// if LineOfSight::Supercover.has_line_of_sight(archer, goblin, &mut provider, ()) {
+///     match LineOfSight::Supercover.line_of_fire(archer, goblin, &mut provider, ()) {
+///         Some(hit) => shoot_at(hit),
+///         None => miss(),
+///     }
+/// }
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineOfSight {
+    /// Trace a [`BresenhamLineInclusiveIter`]. Cheapest, but not symmetric: `a` may see `b`
+    /// while `b` can't see `a`.
+    #[default]
+    Bresenham,
+    /// Trace a [`SupercoverLineIter`], blocked by any opaque cell the line passes through.
+    /// Symmetric, but stricter around the corners of walls.
+    Supercover,
+}
+
+impl LineOfSight {
+    /// Returns true if no opaque cell lies between the origin and the target.
+    ///
+    /// The origin and target themselves never block, so walls can be seen.
+    pub fn has_line_of_sight<T>(
+        &self,
+        origin: IVec2,
+        target: IVec2,
+        provider: &mut impl FovProvider<T>,
+        pass_through_data: T,
+    ) -> bool {
+        let between = |position: &IVec2| *position != target;
+        match self {
+            Self::Bresenham => Self::first_opaque(
+                BresenhamLineInclusiveIter::new(origin, target)
+                    .skip(1)
+                    .take_while(between),
+                provider,
+                pass_through_data,
+            ),
+            Self::Supercover => Self::first_opaque(
+                SupercoverLineIter::new(origin, target)
+                    .skip(1)
+                    .take_while(between),
+                provider,
+                pass_through_data,
+            ),
+        }
+        .is_none()
+    }
+
+    /// Returns the first opaque cell a projectile fired from the origin at the target would hit,
+    /// or `None` if it reaches the target unhindered.
+    ///
+    /// The origin never blocks, the target is returned if it is opaque itself.
+    pub fn line_of_fire<T>(
+        &self,
+        origin: IVec2,
+        target: IVec2,
+        provider: &mut impl FovProvider<T>,
+        pass_through_data: T,
+    ) -> Option<IVec2> {
+        match self {
+            Self::Bresenham => Self::first_opaque(
+                BresenhamLineInclusiveIter::new(origin, target).skip(1),
+                provider,
+                pass_through_data,
+            ),
+            Self::Supercover => Self::first_opaque(
+                SupercoverLineIter::new(origin, target).skip(1),
+                provider,
+                pass_through_data,
+            ),
+        }
+    }
+
+    fn first_opaque<T>(
+        mut line: impl Iterator<Item = IVec2>,
+        provider: &mut impl FovProvider<T>,
+        mut pass_through_data: T,
+    ) -> Option<IVec2> {
+        line.find(|position| provider.is_opaque(*position, &mut pass_through_data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    // a wall with a gap, and a pillar
+    const MAP: [&str; 7] = [
+        "..........",
+        "....#.....",
+        "....#.....",
+        "....#.....",
+        "..........",
+        "........#.",
+        "..........",
+    ];
+
+    fn walls() -> Grid<bool> {
+        let mut walls = Grid::new_copy(UVec2::new(10, 7), false);
+        for (y, row) in MAP.iter().enumerate() {
+            for (x, cell) in row.chars().enumerate() {
+                walls[IVec2::new(x as i32, y as i32)] = cell == '#';
+            }
+        }
+        walls
+    }
+
+    #[test]
+    fn blocked() {
+        let walls = walls();
+        let mut provider = GridFovProvider::new(&walls, |wall: &bool| *wall);
+
+        for los in [LineOfSight::Bresenham, LineOfSight::Supercover] {
+            let (origin, target) = (IVec2::new(1, 2), IVec2::new(8, 2));
+            assert!(!los.has_line_of_sight(origin, target, &mut provider, ()));
+            assert_eq!(
+                los.line_of_fire(origin, target, &mut provider, ()),
+                Some(IVec2::new(4, 2))
+            );
+
+            let (origin, target) = (IVec2::new(1, 4), IVec2::new(9, 4));
+            assert!(los.has_line_of_sight(origin, target, &mut provider, ()));
+            assert_eq!(los.line_of_fire(origin, target, &mut provider, ()), None);
+
+            // walls can be seen, but stop projectiles
+            let target = IVec2::new(8, 5);
+            assert!(los.has_line_of_sight(IVec2::new(5, 5), target, &mut provider, ()));
+            assert_eq!(
+                los.line_of_fire(IVec2::new(5, 5), target, &mut provider, ()),
+                Some(target)
+            );
+        }
+    }
+
+    #[test]
+    fn symmetric() {
+        let walls = walls();
+        let mut provider = GridFovProvider::new(&walls, |wall: &bool| *wall);
+        let cells = Rectangle::new(IVec2::ZERO, IVec2::new(9, 6))
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        for a in &cells {
+            for b in &cells {
+                assert_eq!(
+                    LineOfSight::Supercover.has_line_of_sight(*a, *b, &mut provider, ()),
+                    LineOfSight::Supercover.has_line_of_sight(*b, *a, &mut provider, ()),
+                    "{a} {b}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,3 @@
+//! Provides line of sight checks between two cells, without computing a full field of view.
+mod line_of_sight;
+pub use line_of_sight::*;
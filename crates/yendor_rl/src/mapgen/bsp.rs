@@ -0,0 +1,307 @@
+use std::ops::Range;
+
+use crate::prelude::*;
+
+/// What a [`BspDungeon`] carved into a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum Carve {
+    /// The floor of a room.
+    Room,
+    /// The floor of a corridor between rooms.
+    Corridor,
+    /// A corridor cell right outside a room.
+    Door,
+}
+
+/// The rooms and doors of a map carved by a [`BspDungeon`], for spawning.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Dungeon {
+    /// The floor of every room.
+    pub rooms: Vec<Rectangle>,
+    /// Every cell where a corridor enters a room.
+    pub doors: Vec<IVec2>,
+}
+
+/// Generates rooms and corridors with a binary space partition.
+/// [Basic BSP Dungeon generation](http://www.roguebasin.com/index.php/Basic_BSP_Dungeon_generation)
+///
+/// The map is recursively split in two, a room is carved into every leaf and the rooms of
+/// sibling leaves are connected with L-shaped corridors.
+///
+/// ```ignore
+/// let mut map = Grid::new_copy(UVec2::new(80, 50), Tile::Wall);
+/// let mut rng = StdRng::seed_from_u64(seed);
+/// let dungeon = BspDungeon::default().carve(&mut map, &mut rng, Tile::Floor);
+/// player.position = dungeon.rooms[0].center();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct BspDungeon {
+    max_depth: u32,
+    min_leaf_size: u32,
+    min_room_size: u32,
+    padding: u32,
+}
+
+impl Default for BspDungeon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BspDungeon {
+    /// Creates a new generator, splitting up to 4 times into leaves of at least 8 cells, with
+    /// rooms of at least 3 cells kept 1 cell from the edge of their leaf.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            max_depth: 4,
+            min_leaf_size: 8,
+            min_room_size: 3,
+            padding: 1,
+        }
+    }
+
+    /// Returns the generator splitting the map at most `max_depth` times, for up to
+    /// `2^max_depth` rooms.
+    #[inline]
+    pub const fn with_max_depth(self, max_depth: u32) -> Self {
+        Self { max_depth, ..self }
+    }
+
+    /// Returns the generator never splitting the map into leaves narrower than `min_leaf_size`.
+    #[inline]
+    pub const fn with_min_leaf_size(self, min_leaf_size: u32) -> Self {
+        Self {
+            min_leaf_size,
+            ..self
+        }
+    }
+
+    /// Returns the generator carving rooms at least `min_room_size` wide and tall, space
+    /// permitting.
+    #[inline]
+    pub const fn with_min_room_size(self, min_room_size: u32) -> Self {
+        Self {
+            min_room_size,
+            ..self
+        }
+    }
+
+    /// Returns the generator keeping rooms `padding` cells away from the edge of their leaf.
+    #[inline]
+    pub const fn with_padding(self, padding: u32) -> Self {
+        Self { padding, ..self }
+    }
+
+    /// Carves the rooms, corridors and doors into the grid as `floor`, leaving the edge of the
+    /// grid untouched. The same seed reproduces the same map.
+    pub fn carve<T: Clone>(&self, grid: &mut Grid<T>, rng: &mut impl Rand, floor: T) -> Dungeon {
+        self.carve_with(grid, rng, |_, _| floor.clone())
+    }
+
+    /// Carves the rooms, corridors and doors into the grid as the tile the function returns for
+    /// the position and what is carved there, leaving the edge of the grid untouched. The same
+    /// seed reproduces the same map.
+    pub fn carve_with<T>(
+        &self,
+        grid: &mut Grid<T>,
+        rng: &mut impl Rand,
+        mut carve: impl FnMut(IVec2, Carve) -> T,
+    ) -> Dungeon {
+        let mut dungeon = Dungeon::default();
+        let dimensions = grid.dimensions().as_ivec2();
+        if dimensions.cmplt(IVec2::splat(3)).any() {
+            return dungeon;
+        }
+
+        let mut corridors = Vec::new();
+        let map = Rectangle::new(IVec2::ONE, dimensions - IVec2::splat(2));
+        self.split(map, 0, rng, &mut dungeon.rooms, &mut corridors);
+
+        for room in &dungeon.rooms {
+            room.for_each(|position| grid[position] = carve(position, Carve::Room));
+        }
+
+        let in_room = |position: IVec2| dungeon.rooms.iter().any(|room| room.contains(position));
+        for corridor in &corridors {
+            for position in corridor
+                .iter()
+                .copied()
+                .filter(|position| !in_room(*position))
+            {
+                grid[position] = carve(position, Carve::Corridor);
+            }
+
+            // the corridor cells entering or leaving a room
+            for step in corridor.windows(2) {
+                let door = match (in_room(step[0]), in_room(step[1])) {
+                    (true, false) => step[1],
+                    (false, true) => step[0],
+                    _ => continue,
+                };
+                if !dungeon.doors.contains(&door) {
+                    dungeon.doors.push(door);
+                }
+            }
+        }
+
+        for door in &dungeon.doors {
+            grid[*door] = carve(*door, Carve::Door);
+        }
+
+        dungeon
+    }
+
+    // splits the leaf, returning the range of `rooms` carved into it
+    fn split(
+        &self,
+        leaf: Rectangle,
+        depth: u32,
+        rng: &mut impl Rand,
+        rooms: &mut Vec<Rectangle>,
+        corridors: &mut Vec<Vec<IVec2>>,
+    ) -> Range<usize> {
+        let Some((a, b)) = self.halves(leaf, depth, rng) else {
+            return match self.room(leaf, rng) {
+                Some(room) => {
+                    rooms.push(room);
+                    rooms.len() - 1..rooms.len()
+                }
+                None => rooms.len()..rooms.len(),
+            };
+        };
+
+        let a = self.split(a, depth + 1, rng, rooms, corridors);
+        let b = self.split(b, depth + 1, rng, rooms, corridors);
+        if !a.is_empty() && !b.is_empty() {
+            let start = rooms[rng.range(a.clone())].center();
+            let end = rooms[rng.range(b.clone())].center();
+            corridors.push(Self::corridor(start, end, rng));
+        }
+        a.start..b.end
+    }
+
+    // cuts across the longer side of the leaf, if both halves are large enough
+    fn halves(
+        &self,
+        leaf: Rectangle,
+        depth: u32,
+        rng: &mut impl Rand,
+    ) -> Option<(Rectangle, Rectangle)> {
+        if depth >= self.max_depth {
+            return None;
+        }
+
+        let (columns, rows) = ((leaf.width() + 1) as u32, (leaf.height() + 1) as u32);
+        let min = self.min_leaf_size.max(1);
+        let vertical = match (columns >= 2 * min, rows >= 2 * min) {
+            (false, false) => return None,
+            (true, false) => true,
+            (false, true) => false,
+            (true, true) if columns * 4 > rows * 5 => true,
+            (true, true) if rows * 4 > columns * 5 => false,
+            (true, true) => rng.coin(),
+        };
+
+        let cells = if vertical { columns } else { rows };
+        let ratio = rng.range(min..=cells - min) as f32 / cells as f32;
+        if vertical {
+            leaf.split_vertical(ratio)
+        } else {
+            leaf.split_horizontal(ratio)
+        }
+    }
+
+    // a room of random size and position inside the padding of the leaf
+    fn room(&self, leaf: Rectangle, rng: &mut impl Rand) -> Option<Rectangle> {
+        let inside = leaf.shrink(UVec2::splat(self.padding));
+        if !leaf.contains_rect(inside.expand(UVec2::splat(self.padding))) {
+            return None;
+        }
+
+        let max = UVec2::new(inside.width() as u32 + 1, inside.height() as u32 + 1);
+        let min = UVec2::splat(self.min_room_size.max(1)).min(max);
+        let size = UVec2::new(rng.range(min.x..=max.x), rng.range(min.y..=max.y));
+        let offset = UVec2::new(rng.range(0..=max.x - size.x), rng.range(0..=max.y - size.y));
+
+        let min = inside.min + offset.as_ivec2();
+        Some(Rectangle::new(min, min + size.as_ivec2() - IVec2::ONE))
+    }
+
+    // an L-shaped corridor, turning horizontally or vertically first
+    fn corridor(start: IVec2, end: IVec2, rng: &mut impl Rand) -> Vec<IVec2> {
+        let corner = if rng.coin() {
+            IVec2::new(end.x, start.y)
+        } else {
+            IVec2::new(start.x, end.y)
+        };
+
+        Line::new(start, corner)
+            .iter()
+            .chain(Line::new(corner, end).iter().skip(1))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Tile {
+        Wall,
+        Floor,
+        Door,
+    }
+
+    fn generate(seed: u64) -> (Grid<Tile>, Dungeon) {
+        let mut map = Grid::new_copy(UVec2::new(60, 40), Tile::Wall);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let dungeon = BspDungeon::new().carve_with(&mut map, &mut rng, |_, carve| match carve {
+            Carve::Room | Carve::Corridor => Tile::Floor,
+            Carve::Door => Tile::Door,
+        });
+        (map, dungeon)
+    }
+
+    #[test]
+    fn reproducible() {
+        assert_eq!(generate(7), generate(7));
+        assert_ne!(generate(7).1, generate(8).1);
+    }
+
+    #[test]
+    fn connected() {
+        for seed in 0..16 {
+            let (map, dungeon) = generate(seed);
+            assert!(dungeon.rooms.len() > 4, "{seed}");
+
+            for (index, room) in dungeon.rooms.iter().enumerate() {
+                assert!(Rectangle::new(IVec2::ONE, IVec2::new(58, 38)).contains_rect(*room));
+                for other in &dungeon.rooms[index + 1..] {
+                    assert!(!room.intersects(*other), "{seed}");
+                }
+            }
+            for door in &dungeon.doors {
+                assert_eq!(map[*door], Tile::Door);
+            }
+
+            // every room can be reached from the first one
+            let mut open = vec![dungeon.rooms[0].center()];
+            let mut reached = HashSet::from_iter(open.clone());
+            while let Some(position) = open.pop() {
+                for direction in CardinalDirection::all() {
+                    let next = position + direction.coord();
+                    if map[next] != Tile::Wall && reached.insert(next) {
+                        open.push(next);
+                    }
+                }
+            }
+            for room in &dungeon.rooms {
+                assert!(reached.contains(&room.min), "{seed}");
+            }
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use crate::prelude::*;
+
+/// What a [`CellularCaves`] generator does with the caves cut off from the largest one.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum Disconnected {
+    /// Leave them as they are.
+    Keep,
+    /// Fill them in, leaving only the largest cave.
+    #[default]
+    Remove,
+    /// Dig an L-shaped tunnel from each of them to the largest cave.
+    Connect,
+}
+
+/// Generates natural looking caves with a cellular automaton.
+///
+/// [Cellular Automata Method for Generating Random Cave-Like Levels](http://www.roguebasin.com/index.php/Cellular_Automata_Method_for_Generating_Random_Cave-Like_Levels):
+/// the map starts as random noise, then every iteration a cell becomes a wall if enough of its
+/// 8 neighbours are walls. Cells outside the map count as walls, and the edge of the map is
+/// always a wall.
+///
+/// ```ignore
+/// let mut rng = StdRng::seed_from_u64(seed);
+/// let walls = CellularCaves::default().generate(UVec2::new(80, 50), &mut rng);
+/// let mut provider = GridFovProvider::new(&walls, |wall: &bool| *wall);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct CellularCaves {
+    fill: f64,
+    birth: u8,
+    survival: u8,
+    iterations: u32,
+    disconnected: Disconnected,
+}
+
+impl Default for CellularCaves {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CellularCaves {
+    /// Creates a new generator filling 45% of the map with walls, then running 4 iterations of
+    /// the `B5678/S45678` rules and removing disconnected caves.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            fill: 0.45,
+            birth: 5,
+            survival: 4,
+            iterations: 4,
+            disconnected: Disconnected::Remove,
+        }
+    }
+
+    /// Returns the generator starting with `fill` of the map, between `0` and `1`, as walls.
+    #[inline]
+    pub const fn with_fill(self, fill: f64) -> Self {
+        Self { fill, ..self }
+    }
+
+    /// Returns the generator turning open cells with at least `birth` wall neighbours into walls
+    /// and keeping walls with at least `survival` wall neighbours.
+    #[inline]
+    pub const fn with_rules(self, birth: u8, survival: u8) -> Self {
+        Self {
+            birth,
+            survival,
+            ..self
+        }
+    }
+
+    /// Returns the generator running the rules `iterations` times.
+    #[inline]
+    pub const fn with_iterations(self, iterations: u32) -> Self {
+        Self { iterations, ..self }
+    }
+
+    /// Returns the generator handling the caves cut off from the largest one as `disconnected`.
+    #[inline]
+    pub const fn with_disconnected(self, disconnected: Disconnected) -> Self {
+        Self {
+            disconnected,
+            ..self
+        }
+    }
+
+    /// Generates the caves, `true` cells being walls. The same seed reproduces the same map.
+    pub fn generate(&self, dimensions: UVec2, rng: &mut impl Rand) -> BitGrid {
+        let mut walls = BitGrid::new_default(dimensions);
+        for position in walls.point_iter() {
+            let wall = Self::is_edge(position, dimensions) || rng.float() < self.fill;
+            walls.set_unchecked(position.as_uvec2(), wall);
+        }
+
+        for _ in 0..self.iterations {
+            walls = self.step(&walls);
+        }
+
+        match self.disconnected {
+            Disconnected::Keep => {}
+            Disconnected::Remove => Self::remove_disconnected(&mut walls),
+            Disconnected::Connect => Self::connect_disconnected(&mut walls),
+        }
+        walls
+    }
+
+    // applies the rules to every cell at once
+    fn step(&self, walls: &BitGrid) -> BitGrid {
+        let dimensions = walls.dimensions();
+        BitGrid::new_fn(dimensions, |(_, position)| {
+            if Self::is_edge(position, dimensions) {
+                return true;
+            }
+
+            let neighbours = DirectionIter::all_2d()
+                .filter(|direction| {
+                    let neighbour = position + direction.coord();
+                    !neighbour.is_valid(dimensions) || walls[neighbour]
+                })
+                .count() as u8;
+            if walls[position] {
+                neighbours >= self.survival
+            } else {
+                neighbours >= self.birth
+            }
+        })
+    }
+
+    fn remove_disconnected(walls: &mut BitGrid) {
+        let regions = walls.label_regions(|wall| !*wall, Connectivity::Cardinal);
+        let Some(largest) = regions.largest_region() else {
+            return;
+        };
+
+        let cave = regions.mask(largest.id);
+        for position in walls.point_iter() {
+            if !cave[position] {
+                walls.set_unchecked(position.as_uvec2(), true);
+            }
+        }
+    }
+
+    fn connect_disconnected(walls: &mut BitGrid) {
+        let regions = walls.label_regions(|wall| !*wall, Connectivity::Cardinal);
+        let mut caves = vec![Vec::new(); regions.regions().len()];
+        for position in walls.point_iter() {
+            match regions.labels()[position] {
+                0 => {}
+                id => caves[id as usize - 1].push(position),
+            }
+        }
+
+        caves.sort_by_key(|cave| std::cmp::Reverse(cave.len()));
+        let Some((largest, rest)) = caves.split_first() else {
+            return;
+        };
+
+        let closest = |cells: &[IVec2], target: IVec2| {
+            cells
+                .iter()
+                .copied()
+                .min_by_key(|cell| {
+                    let offset = (*cell - target).abs();
+                    offset.x + offset.y
+                })
+                .unwrap_or(target)
+        };
+        for cave in rest {
+            // close enough to the shortest tunnel, without comparing every pair of cells
+            let end = closest(largest, cave[0]);
+            let start = closest(cave, end);
+            let corner = IVec2::new(end.x, start.y);
+            for position in Line::new(start, corner)
+                .iter()
+                .chain(Line::new(corner, end).iter())
+            {
+                walls.set_unchecked(position.as_uvec2(), false);
+            }
+        }
+    }
+
+    #[inline]
+    const fn is_edge(position: IVec2, dimensions: UVec2) -> bool {
+        position.x == 0
+            || position.y == 0
+            || position.x == dimensions.x as i32 - 1
+            || position.y == dimensions.y as i32 - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn render(walls: &BitGrid) -> Vec<String> {
+        (0..walls.height() as i32)
+            .map(|y| {
+                (0..walls.width() as i32)
+                    .map(|x| if walls[IVec2::new(x, y)] { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn generate(disconnected: Disconnected, seed: u64) -> BitGrid {
+        let mut rng = StdRng::seed_from_u64(seed);
+        CellularCaves::new()
+            .with_disconnected(disconnected)
+            .generate(UVec2::new(40, 16), &mut rng)
+    }
+
+    #[test]
+    fn remove() {
+        assert_eq!(
+            render(&generate(Disconnected::Remove, 0xca7e)),
+            [
+                "########################################",
+                "########################################",
+                "#######..###############################",
+                "######....#####..#######################",
+                "#####.....####....######################",
+                "####.......##.....######################",
+                "##...............#######################",
+                "#................#######################",
+                "#...............########################",
+                "#...............########################",
+                "#...............########################",
+                "##...............#######################",
+                "##...............#######################",
+                "##........##.....#######################",
+                "###......#####..########################",
+                "########################################",
+            ]
+        );
+    }
+
+    #[test]
+    fn connect() {
+        assert_eq!(
+            render(&generate(Disconnected::Connect, 0xca7e)),
+            [
+                "########################################",
+                "####################....################",
+                "#######..###########.....###############",
+                "######....#####...........##...##....###",
+                "#####.....####....###................###",
+                "####.......##.....####...............###",
+                "##...............#####...........#######",
+                "#................####...........########",
+                "#...............####............########",
+                "#...............###.............########",
+                "#...............####.......##...########",
+                "##...................................###",
+                "##...............#####....###..####..###",
+                "##........##.....#####....##############",
+                "###......#####..#######..###############",
+                "########################################",
+            ]
+        );
+
+        for seed in 0..16 {
+            let walls = generate(Disconnected::Connect, seed);
+            let open = walls.point_iter().filter(|position| !walls[*position]);
+            let kept = generate(Disconnected::Keep, seed);
+            assert!(open.count() >= kept.iter().filter(|wall| !**wall).count());
+            let regions = walls.label_regions(|wall| !*wall, Connectivity::Cardinal);
+            assert_eq!(regions.regions().len(), 1, "{seed}");
+        }
+    }
+}
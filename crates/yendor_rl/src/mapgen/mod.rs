@@ -0,0 +1,9 @@
+//! Provides procedural map generators.
+mod bsp;
+pub use bsp::*;
+mod cellular_caves;
+pub use cellular_caves::*;
+mod prefab;
+pub use prefab::*;
+mod wave_function_collapse;
+pub use wave_function_collapse::*;
@@ -0,0 +1,245 @@
+use crate::prelude::*;
+
+/// A hand-designed vault, parsed from an ASCII template and stamped into a larger map.
+///
+/// ```ignore
+/// let vault = Prefab::parse(
+///     "
+/// #####
+/// #...+
+/// #.$.#
+/// #####",
+///     Tile::from_glyph,
+/// );
+/// for octant in (0..8).map(Octant) {
+///     if vault.transform(octant).stamp(&mut map, position, |tile| *tile == Tile::Wall) {
+///         break;
+///     }
+/// }
+/// ```
+#[derive(Default, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Prefab<T> {
+    grid: Grid<T>,
+}
+
+impl<T> Prefab<T> {
+    /// Creates a new prefab from a grid.
+    #[inline]
+    pub const fn new(grid: Grid<T>) -> Self {
+        Self { grid }
+    }
+
+    /// Parses a template, one row per line from the top, with the same layout as a
+    /// `yendor_utility::canvas::Canvas`. Blank lines before and after the template are skipped,
+    /// so the closing quote may be indented, empty lines within it are rows of spaces, and short
+    /// lines are padded with spaces.
+    pub fn parse(template: &str, mut glyph: impl FnMut(char) -> T) -> Self {
+        let mut lines = template
+            .lines()
+            .skip_while(|line| line.trim().is_empty())
+            .collect::<Vec<_>>();
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+        let lines = lines
+            .into_iter()
+            .map(|line| line.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let width = lines.iter().map(Vec::len).max().unwrap_or_default();
+
+        let cells = lines
+            .iter()
+            .flat_map(|line| (0..width).map(|x| line.get(x).copied().unwrap_or(' ')))
+            .map(&mut glyph)
+            .collect();
+        Self::new(Grid::new(
+            UVec2::new(width as u32, lines.len() as u32),
+            cells,
+        ))
+    }
+
+    /// Returns the tiles of the prefab.
+    #[inline]
+    pub const fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    /// Returns the dimensions of the prefab.
+    #[inline]
+    pub fn dimensions(&self) -> UVec2 {
+        self.grid.dimensions()
+    }
+
+    /// Returns true if the prefab has no cells.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.dimensions().cmpeq(UVec2::ZERO).any()
+    }
+
+    /// Returns the cells the prefab covers when stamped at `position`.
+    #[inline]
+    pub fn bounds(&self, position: IVec2) -> Rectangle {
+        Rectangle::new(
+            position,
+            position + self.dimensions().as_ivec2() - IVec2::ONE,
+        )
+    }
+}
+
+impl<T: Clone> Prefab<T> {
+    /// Returns the prefab rotated and mirrored as [`Octant::to_offset`] maps `Octant(0)` to the
+    /// octant, covering all eight rotations and reflections.
+    pub fn transform(&self, octant: Octant) -> Self {
+        if self.is_empty() {
+            return self.clone();
+        }
+
+        let dimensions = self.dimensions();
+        let transform = |position: IVec2| {
+            let (x, y) = octant.to_offset(position);
+            IVec2::new(x, y)
+        };
+        let far = transform(dimensions.as_ivec2() - IVec2::ONE);
+        let min = far.min(IVec2::ZERO);
+        let transformed = far.abs().as_uvec2() + UVec2::ONE;
+
+        let mut cells = vec![None; transformed.x as usize * transformed.y as usize];
+        for (index, tile) in self.grid.iter().enumerate() {
+            let position = transform(self.grid.index_to_pt_unchecked(index).as_ivec2()) - min;
+            cells[position.as_uvec2().as_index_unchecked(transformed.x)] = Some(tile.clone());
+        }
+        Self::new(Grid::new(
+            transformed,
+            cells.into_iter().flatten().collect(),
+        ))
+    }
+
+    /// Returns true if the prefab lies within the grid at `position` and every cell it covers is
+    /// free to be overwritten. An empty prefab covers no cells, so it fits anywhere.
+    pub fn fits(
+        &self,
+        grid: &Grid<T>,
+        position: IVec2,
+        mut is_free: impl FnMut(&T) -> bool,
+    ) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+
+        let bounds = self.bounds(position);
+        position.is_valid(grid.dimensions())
+            && bounds.max.is_valid(grid.dimensions())
+            && bounds.into_iter().all(|cell| is_free(&grid[cell]))
+    }
+
+    /// Stamps the prefab into the grid at `position` if it [fits](Prefab::fits), returning
+    /// whether it was stamped.
+    pub fn stamp(
+        &self,
+        grid: &mut Grid<T>,
+        position: IVec2,
+        is_free: impl FnMut(&T) -> bool,
+    ) -> bool {
+        if !self.fits(grid, position, is_free) {
+            return false;
+        }
+        if self.is_empty() {
+            return true;
+        }
+
+        grid.blit_clone(
+            position.as_uvec2(),
+            &self.grid,
+            UVec2::ZERO,
+            self.dimensions(),
+        );
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    const VAULT: &str = "
+###
+#.+
+#..
+#..
+";
+
+    fn render(grid: &Grid<char>) -> Vec<String> {
+        grid.rows().map(String::from_iter).collect()
+    }
+
+    #[test]
+    fn transform() {
+        let vault = Prefab::parse(VAULT, |glyph| glyph);
+        assert_eq!(vault.dimensions(), UVec2::new(3, 4));
+        assert_eq!(render(vault.grid()), ["###", "#.+", "#..", "#.."]);
+
+        let transforms = (0..8)
+            .map(|octant| render(vault.transform(Octant(octant)).grid()))
+            .collect::<Vec<_>>();
+        assert_eq!(transforms[0], render(vault.grid()));
+        assert_eq!(transforms[1], ["####", "#...", "#+.."]);
+        assert_eq!(transforms[3], ["###", "+.#", "..#", "..#"]);
+        assert_eq!(transforms[7], ["#..", "#..", "#.+", "###"]);
+
+        // every transform is distinct, and transforming back restores the vault
+        assert_eq!(transforms.iter().collect::<HashSet<_>>().len(), 8);
+        for octant in 0..8 {
+            let transformed = vault.transform(Octant(octant));
+            let inverse = (0..8)
+                .map(|inverse| transformed.transform(Octant(inverse)))
+                .any(|restored| restored == vault);
+            assert!(inverse, "{octant}");
+        }
+    }
+
+    #[test]
+    fn stamp() {
+        let mut map = Grid::new_copy(UVec2::new(8, 6), ' ');
+        map[IVec2::new(6, 1)] = '@';
+        let vault = Prefab::parse(VAULT, |glyph| glyph);
+        let is_free = |tile: &char| *tile == ' ';
+
+        assert!(!vault.stamp(&mut map, IVec2::new(6, 0), is_free));
+        assert!(!vault.stamp(&mut map, IVec2::new(-1, 0), is_free));
+        assert!(!vault.stamp(&mut map, IVec2::new(4, 0), is_free));
+        assert!(vault.stamp(&mut map, IVec2::new(1, 1), is_free));
+        assert!(!vault.stamp(&mut map, IVec2::ZERO, is_free));
+        assert!(vault
+            .transform(Octant(1))
+            .stamp(&mut map, IVec2::new(4, 3), is_free));
+        assert_eq!(
+            render(&map),
+            ["        ", " ###  @ ", " #.+    ", " #..####", " #..#...", "    #+..",]
+        );
+
+        // an empty prefab covers nothing, wherever it is stamped
+        let empty = Prefab::parse("\n\n", |glyph| glyph);
+        assert!(empty.is_empty());
+        assert!(empty.fits(&map, IVec2::new(-3, 20), is_free));
+        assert!(empty.stamp(&mut map, IVec2::new(1, 1), is_free));
+        assert_eq!(map[IVec2::new(1, 1)], '#');
+    }
+
+    #[test]
+    fn parse() {
+        let prefab = Prefab::parse("\n\n#.#\n\n.\n\n", |glyph| glyph);
+        assert_eq!(render(prefab.grid()), ["#.#", "   ", ".  "]);
+
+        let indented = Prefab::parse(
+            "
+#.#
+
+.
+    ",
+            |glyph| glyph,
+        );
+        assert_eq!(render(indented.grid()), ["#.#", "   ", ".  "]);
+        let padded = Prefab::parse("  \n\t\n#.#\n \n", |glyph| glyph);
+        assert_eq!(render(padded.grid()), ["#.#"]);
+    }
+}
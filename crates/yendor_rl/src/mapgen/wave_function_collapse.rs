@@ -0,0 +1,590 @@
+use std::{cmp::Ordering, collections::BinaryHeap, hash::Hash};
+
+use crate::prelude::*;
+
+/// The reasons a [`WaveFunctionCollapse`] generator can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WfcError {
+    /// The sample has no patterns to learn from.
+    EmptySample,
+    /// No arrangement of the patterns fits the constraints.
+    Contradiction,
+    /// The generator gave up after backtracking too many times.
+    BacktrackLimitExceeded,
+}
+
+impl Display for WfcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptySample => write!(f, "the sample has no patterns"),
+            Self::Contradiction => write!(f, "no arrangement satisfies the constraints"),
+            Self::BacktrackLimitExceeded => write!(f, "backtrack limit exceeded"),
+        }
+    }
+}
+
+impl std::error::Error for WfcError {}
+
+/// Generates maps resembling a sample, using wave function collapse.
+/// [WaveFunctionCollapse](https://github.com/mxgmn/WaveFunctionCollapse)
+///
+/// The generator learns which patterns of the sample may be next to each other in every
+/// [`CardinalDirection`], then repeatedly picks the cell with the fewest possible patterns left,
+/// settles it on one of them and removes the patterns its neighbours can no longer hold. When a
+/// cell runs out of patterns, the last choices are undone and other patterns tried instead.
+///
+/// ```ignore
+/// let generator = WaveFunctionCollapse::overlapping(&sample, 3);
+/// let mut rng = StdRng::seed_from_u64(seed);
+/// let map = generator.generate(UVec2::new(80, 50), &mut rng)?;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaveFunctionCollapse<T> {
+    // the tile each pattern places in its cell
+    tiles: Vec<T>,
+    weights: Vec<f64>,
+    // `compatible[direction][a]` lists the patterns that may be in `direction` of pattern `a`
+    compatible: Vec<Vec<Vec<usize>>>,
+    max_backtracks: u32,
+}
+
+impl<T: Clone + Eq + Hash> WaveFunctionCollapse<T> {
+    /// Creates a new generator learning from the simple tiled model: the patterns are the tiles
+    /// of the sample, and two tiles may be next to each other if they are somewhere in the
+    /// sample.
+    pub fn simple(sample: &Grid<T>) -> Self {
+        let mut indices = HashMap::default();
+        let mut tiles = Vec::new();
+        let mut weights = Vec::new();
+        let patterns = sample
+            .iter()
+            .map(|tile| {
+                let index = *indices.entry(tile.clone()).or_insert_with(|| {
+                    tiles.push(tile.clone());
+                    weights.push(0.0);
+                    tiles.len() - 1
+                });
+                weights[index] += 1.0;
+                index
+            })
+            .collect::<Vec<_>>();
+
+        let dimensions = sample.dimensions();
+        let mut compatible = vec![vec![vec![false; tiles.len()]; tiles.len()]; 4];
+        for position in sample.point_iter() {
+            for (direction, offset) in Self::offsets().enumerate() {
+                let neighbour = position + offset;
+                if neighbour.is_valid(dimensions) {
+                    let a = patterns[position.as_index_unchecked(dimensions.x)];
+                    let b = patterns[neighbour.as_index_unchecked(dimensions.x)];
+                    compatible[direction][a][b] = true;
+                }
+            }
+        }
+
+        Self::new(tiles, weights, compatible)
+    }
+
+    /// Creates a new generator learning from the overlapping model: the patterns are every
+    /// `size` by `size` window of the sample, and two patterns may be next to each other if
+    /// they agree where they overlap. Each cell holds the tile in the corner of its pattern.
+    pub fn overlapping(sample: &Grid<T>, size: u32) -> Self {
+        let size = size.max(1) as i32;
+        let windows = (sample.dimensions().as_ivec2() - IVec2::splat(size - 1)).max(IVec2::ZERO);
+
+        let mut indices = HashMap::default();
+        let mut patterns = Vec::new();
+        let mut weights = Vec::new();
+        for origin in Rectangle::new_with_size(IVec2::ZERO, windows.as_uvec2())
+            .into_iter()
+            .filter(|origin| origin.cmplt(windows).all())
+        {
+            let pattern = Rectangle::new_with_size(origin, UVec2::splat(size as u32 - 1))
+                .into_iter()
+                .map(|position| sample[position].clone())
+                .collect::<Vec<_>>();
+            let index = *indices.entry(pattern.clone()).or_insert_with(|| {
+                patterns.push(pattern);
+                weights.push(0.0);
+                patterns.len() - 1
+            });
+            weights[index] += 1.0;
+        }
+
+        let window = Rectangle::new(IVec2::ZERO, IVec2::splat(size - 1));
+        let index = |position: IVec2| (position.y * size + position.x) as usize;
+        let compatible = Self::offsets()
+            .map(|offset| {
+                patterns
+                    .iter()
+                    .map(|a| {
+                        patterns
+                            .iter()
+                            .map(|b| {
+                                // `b` is shifted by `offset`, compare the cells they share
+                                window
+                                    .into_iter()
+                                    .filter(|position| window.contains(*position - offset))
+                                    .all(|position| {
+                                        a[index(position)] == b[index(position - offset)]
+                                    })
+                            })
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let tiles = patterns
+            .into_iter()
+            .map(|pattern| pattern[0].clone())
+            .collect();
+        Self::new(tiles, weights, compatible)
+    }
+
+    fn new(tiles: Vec<T>, weights: Vec<f64>, compatible: Vec<Vec<Vec<bool>>>) -> Self {
+        let compatible = compatible
+            .into_iter()
+            .map(|direction| {
+                direction
+                    .into_iter()
+                    .map(|allowed| (0..allowed.len()).filter(|b| allowed[*b]).collect())
+                    .collect()
+            })
+            .collect();
+        Self {
+            tiles,
+            weights,
+            compatible,
+            max_backtracks: 1000,
+        }
+    }
+}
+
+impl<T: Clone> WaveFunctionCollapse<T> {
+    /// Returns the generator giving up after backtracking `max_backtracks` times.
+    #[inline]
+    pub fn with_max_backtracks(self, max_backtracks: u32) -> Self {
+        Self {
+            max_backtracks,
+            ..self
+        }
+    }
+
+    /// Returns the number of patterns learned from the sample.
+    #[inline]
+    pub const fn pattern_count(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Generates a new grid. The same seed reproduces the same grid.
+    pub fn generate(&self, dimensions: UVec2, rng: &mut impl Rand) -> Result<Grid<T>, WfcError> {
+        let patterns = self.solve(dimensions, rng)?;
+        Ok(Grid::new(
+            dimensions,
+            patterns
+                .into_iter()
+                .map(|pattern| self.tiles[pattern].clone())
+                .collect(),
+        ))
+    }
+
+    /// Fills every cell of the grid. The grid is left untouched if generation fails.
+    pub fn fill(&self, grid: &mut Grid<T>, rng: &mut impl Rand) -> Result<(), WfcError> {
+        let patterns = self.solve(grid.dimensions(), rng)?;
+        for (cell, pattern) in grid.iter_mut().zip(patterns) {
+            *cell = self.tiles[pattern].clone();
+        }
+        Ok(())
+    }
+
+    // the pattern of every cell, in row-major order
+    fn solve(&self, dimensions: UVec2, rng: &mut impl Rand) -> Result<Vec<usize>, WfcError> {
+        if self.tiles.is_empty() {
+            return Err(WfcError::EmptySample);
+        }
+
+        let mut wave = Wave::new(self, dimensions, rng);
+        if !wave.propagate(rng) {
+            return Err(WfcError::Contradiction);
+        }
+
+        let mut decisions: Vec<Decision> = Vec::new();
+        let mut backtracks = 0;
+        while let Some(cell) = wave.lowest_entropy() {
+            let pattern = wave.choose(cell, rng);
+            decisions.push(Decision {
+                trail: wave.trail.len(),
+                cell,
+                pattern,
+            });
+
+            let mut settled = wave.collapse(cell, pattern, rng) && wave.propagate(rng);
+            while !settled {
+                let decision = decisions.pop().ok_or(WfcError::Contradiction)?;
+                backtracks += 1;
+                if backtracks > self.max_backtracks {
+                    return Err(WfcError::BacktrackLimitExceeded);
+                }
+
+                // the choice failed, rule it out under the choices before it
+                wave.undo(decision.trail, rng);
+                settled = wave.remove(decision.cell, decision.pattern, rng) && wave.propagate(rng);
+            }
+        }
+
+        Ok(wave.patterns())
+    }
+
+    #[inline]
+    fn offsets() -> impl Iterator<Item = IVec2> {
+        CardinalDirection::all().map(Direction::coord)
+    }
+}
+
+// a pattern chosen for a cell, and the length of the trail before it was chosen
+struct Decision {
+    trail: usize,
+    cell: usize,
+    pattern: usize,
+}
+
+// the patterns every cell can still hold
+struct Wave<'a, T> {
+    model: &'a WaveFunctionCollapse<T>,
+    dimensions: UVec2,
+    possible: Vec<bool>,
+    counts: Vec<usize>,
+    // the sums of the weights and of `weight * ln(weight)` of the possible patterns of a cell
+    sums: Vec<(f64, f64)>,
+    // `supports[(cell * patterns + b) * 4 + direction]` counts the patterns of the cell behind
+    // `cell` in `direction` that allow `b`, when it drops to zero `b` is removed
+    supports: Vec<u32>,
+    // every pattern removed from a cell, to undo choices
+    trail: Vec<(usize, usize)>,
+    // the patterns which lost their last support
+    pending: Vec<(usize, usize)>,
+    // the unsettled cells, possibly with stale entries for cells which changed since
+    entropies: BinaryHeap<EntropyNode>,
+}
+
+impl<'a, T: Clone> Wave<'a, T> {
+    fn new(model: &'a WaveFunctionCollapse<T>, dimensions: UVec2, rng: &mut impl Rand) -> Self {
+        let cells = dimensions.size();
+        let patterns = model.tiles.len();
+        let sum = model.weights.iter().sum::<f64>();
+        let sum_log = model
+            .weights
+            .iter()
+            .map(|weight| weight * weight.ln())
+            .sum::<f64>();
+
+        let mut supports = vec![0; patterns * 4];
+        for (direction, compatible) in model.compatible.iter().enumerate() {
+            for b in compatible.iter().flatten() {
+                supports[b * 4 + direction] += 1;
+            }
+        }
+
+        let mut wave = Self {
+            model,
+            dimensions,
+            possible: vec![true; cells * patterns],
+            counts: vec![patterns; cells],
+            sums: vec![(sum, sum_log); cells],
+            supports: supports.repeat(cells),
+            trail: Vec::new(),
+            pending: Vec::new(),
+            entropies: BinaryHeap::with_capacity(cells),
+        };
+
+        // patterns nothing allows next to them can only be at the edges
+        for cell in 0..cells {
+            let position = wave.position(cell);
+            wave.push_entropy(cell, rng);
+            for (direction, offset) in WaveFunctionCollapse::<T>::offsets().enumerate() {
+                if (position - offset).is_valid(dimensions) {
+                    wave.pending.extend(
+                        (0..patterns)
+                            .filter(|b| supports[b * 4 + direction] == 0)
+                            .map(|b| (cell, b)),
+                    );
+                }
+            }
+        }
+        wave
+    }
+
+    #[inline]
+    fn is_possible(&self, cell: usize, pattern: usize) -> bool {
+        self.possible[cell * self.model.tiles.len() + pattern]
+    }
+
+    #[inline]
+    const fn position(&self, cell: usize) -> IVec2 {
+        let width = self.dimensions.x as usize;
+        IVec2::new((cell % width) as i32, (cell / width) as i32)
+    }
+
+    #[inline]
+    fn entropy(&self, cell: usize) -> f64 {
+        let (sum, sum_log) = self.sums[cell];
+        sum.ln() - sum_log / sum
+    }
+
+    // queues the cell with its current entropy, ties broken at random
+    fn push_entropy(&mut self, cell: usize, rng: &mut impl Rand) {
+        if self.counts[cell] > 1 {
+            let entropy = self.entropy(cell);
+            self.entropies.push(EntropyNode {
+                cell,
+                entropy,
+                noise: rng.float() * 1e-6,
+            });
+        }
+    }
+
+    // the unsettled cell with the lowest entropy
+    fn lowest_entropy(&mut self) -> Option<usize> {
+        while let Some(node) = self.entropies.pop() {
+            if self.counts[node.cell] > 1 && node.entropy == self.entropy(node.cell) {
+                return Some(node.cell);
+            }
+        }
+        None
+    }
+
+    // a possible pattern, picked by how often it occurs in the sample
+    fn choose(&self, cell: usize, rng: &mut impl Rand) -> usize {
+        let possible = (0..self.model.tiles.len())
+            .filter(|pattern| self.is_possible(cell, *pattern))
+            .collect::<Vec<_>>();
+
+        let mut roll = rng.float() * self.sums[cell].0;
+        for pattern in &possible {
+            roll -= self.model.weights[*pattern];
+            if roll < 0.0 {
+                return *pattern;
+            }
+        }
+        possible[possible.len() - 1]
+    }
+
+    // returns `false` if the cell has no patterns left
+    fn collapse(&mut self, cell: usize, pattern: usize, rng: &mut impl Rand) -> bool {
+        (0..self.model.tiles.len())
+            .filter(|other| *other != pattern)
+            .all(|other| !self.is_possible(cell, other) || self.remove(cell, other, rng))
+    }
+
+    // returns `false` if the cell has no patterns left
+    fn remove(&mut self, cell: usize, pattern: usize, rng: &mut impl Rand) -> bool {
+        let weight = self.model.weights[pattern];
+        self.possible[cell * self.model.tiles.len() + pattern] = false;
+        self.counts[cell] -= 1;
+        self.sums[cell].0 -= weight;
+        self.sums[cell].1 -= weight * weight.ln();
+        self.trail.push((cell, pattern));
+        self.push_entropy(cell, rng);
+
+        let patterns = self.model.tiles.len();
+        let position = self.position(cell);
+        for (direction, offset) in WaveFunctionCollapse::<T>::offsets().enumerate() {
+            let neighbour = position + offset;
+            if !neighbour.is_valid(self.dimensions) {
+                continue;
+            }
+
+            let neighbour = neighbour.as_index_unchecked(self.dimensions.x);
+            for b in &self.model.compatible[direction][pattern] {
+                let support = &mut self.supports[(neighbour * patterns + b) * 4 + direction];
+                *support -= 1;
+                if *support == 0 && self.possible[neighbour * patterns + b] {
+                    self.pending.push((neighbour, *b));
+                }
+            }
+        }
+        self.counts[cell] > 0
+    }
+
+    // removes the patterns no longer supported by a neighbour, returning `false` on a
+    // contradiction
+    fn propagate(&mut self, rng: &mut impl Rand) -> bool {
+        while let Some((cell, pattern)) = self.pending.pop() {
+            if self.is_possible(cell, pattern) && !self.remove(cell, pattern, rng) {
+                self.pending.clear();
+                return false;
+            }
+        }
+        true
+    }
+
+    fn undo(&mut self, trail: usize, rng: &mut impl Rand) {
+        let patterns = self.model.tiles.len();
+        while self.trail.len() > trail {
+            let Some((cell, pattern)) = self.trail.pop() else {
+                break;
+            };
+            let weight = self.model.weights[pattern];
+            self.possible[cell * patterns + pattern] = true;
+            self.counts[cell] += 1;
+            self.sums[cell].0 += weight;
+            self.sums[cell].1 += weight * weight.ln();
+            self.push_entropy(cell, rng);
+
+            let position = self.position(cell);
+            for (direction, offset) in WaveFunctionCollapse::<T>::offsets().enumerate() {
+                let neighbour = position + offset;
+                if neighbour.is_valid(self.dimensions) {
+                    let neighbour = neighbour.as_index_unchecked(self.dimensions.x);
+                    for b in &self.model.compatible[direction][pattern] {
+                        self.supports[(neighbour * patterns + b) * 4 + direction] += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    // the pattern every cell settled on
+    fn patterns(&self) -> Vec<usize> {
+        (0..self.counts.len())
+            .map(|cell| {
+                (0..self.model.tiles.len())
+                    .find(|pattern| self.is_possible(cell, *pattern))
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+}
+
+/// Entry in the entropy queue, ordered so the `BinaryHeap` pops the lowest entropy first.
+struct EntropyNode {
+    cell: usize,
+    entropy: f64,
+    noise: f64,
+}
+
+impl PartialEq for EntropyNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for EntropyNode {}
+
+impl PartialOrd for EntropyNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EntropyNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.entropy + other.noise).total_cmp(&(self.entropy + self.noise))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn grid(rows: &[&str]) -> Grid<char> {
+        let mut grid = Grid::new_copy(UVec2::new(rows[0].len() as u32, rows.len() as u32), ' ');
+        for (y, row) in rows.iter().enumerate() {
+            for (x, cell) in row.chars().enumerate() {
+                grid[IVec2::new(x as i32, y as i32)] = cell;
+            }
+        }
+        grid
+    }
+
+    // every `size` by `size` window of the grid
+    fn windows(grid: &Grid<char>, size: i32) -> HashSet<Vec<char>> {
+        let dimensions = grid.dimensions().as_ivec2();
+        Rectangle::new(IVec2::ZERO, dimensions - IVec2::splat(size))
+            .into_iter()
+            .map(|origin| {
+                Rectangle::new(origin, origin + IVec2::splat(size - 1))
+                    .into_iter()
+                    .map(|position| grid[position])
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn simple() {
+        let sample = grid(&["#.#.", ".#.#", "#.#."]);
+        let generator = WaveFunctionCollapse::simple(&sample);
+        assert_eq!(generator.pattern_count(), 2);
+
+        let mut rng = StdRng::seed_from_u64(5);
+        let map = generator.generate(UVec2::new(9, 7), &mut rng).unwrap();
+        for position in map.point_iter() {
+            for direction in CardinalDirection::all() {
+                let neighbour = position + direction.coord();
+                if neighbour.is_valid(map.dimensions()) {
+                    assert_ne!(map[position], map[neighbour]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn overlapping() {
+        let sample = grid(&[
+            "........", //
+            ".###....", //
+            ".#.#....", //
+            ".###..#.", //
+            "......#.", //
+            "....###.", //
+            "........", //
+        ]);
+        let generator = WaveFunctionCollapse::overlapping(&sample, 3);
+        let expected = windows(&sample, 3);
+        assert_eq!(generator.pattern_count(), expected.len());
+
+        for seed in 0..4 {
+            let mut map = Grid::new_copy(UVec2::new(16, 12), ' ');
+            let mut rng = StdRng::seed_from_u64(seed);
+            generator.fill(&mut map, &mut rng).unwrap();
+            assert!(!map.iter().any(|cell| *cell == ' '));
+
+            let mut rng = StdRng::seed_from_u64(seed);
+            assert_eq!(
+                generator.generate(map.dimensions(), &mut rng).as_ref(),
+                Ok(&map)
+            );
+            assert!(windows(&map, 3).is_subset(&expected), "{seed}");
+        }
+    }
+
+    #[test]
+    fn contradiction() {
+        let generator = WaveFunctionCollapse::simple(&grid(&["ab"]));
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(generator.generate(UVec2::new(2, 1), &mut rng).is_ok());
+        assert_eq!(
+            generator.generate(UVec2::new(3, 1), &mut rng),
+            Err(WfcError::Contradiction)
+        );
+
+        // every cell has a pattern its neighbours allow, so this takes backtracking to refute
+        let generator = WaveFunctionCollapse::simple(&grid(&["aba", "bda", "cda"]));
+        assert_eq!(
+            generator.generate(UVec2::splat(5), &mut rng),
+            Err(WfcError::Contradiction)
+        );
+        assert_eq!(
+            generator
+                .with_max_backtracks(0)
+                .generate(UVec2::splat(5), &mut rng),
+            Err(WfcError::BacktrackLimitExceeded)
+        );
+        assert_eq!(
+            WaveFunctionCollapse::overlapping(&grid(&["ab"]), 2).generate(UVec2::ONE, &mut rng),
+            Err(WfcError::EmptySample)
+        );
+    }
+}
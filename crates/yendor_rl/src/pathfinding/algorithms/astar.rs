@@ -4,22 +4,22 @@ use crate::prelude::*;
 pub struct AStar;
 
 impl PathAlgorithm for AStar {
-    fn compute_path<T>(
+    fn compute_path_limited<T>(
         origin: IVec2,
         destination: IVec2,
         provider: &mut impl PathProvider<T>,
         mut pass_through_data: T,
-    ) -> Vec<IVec2> {
+        limits: SearchLimits,
+    ) -> Result<PathResult, PathError> {
+        let mut budget = SearchBudget::new(origin, limits);
         let result = astar(
             &origin,
-            |&p| provider.generate_successors(p, &mut pass_through_data),
+            |&p| budget.successors(p, provider.generate_successors(p, &mut pass_through_data)),
             |&p| provider.distance(p, destination),
             |&p| p == destination,
         );
 
-        match result {
-            Some((path, _length)) => path,
-            None => Vec::new(),
-        }
+        budget
+            .finish(result.map(|(path, cost)| PathResult::new(path, cost, budget.nodes_expanded())))
     }
 }
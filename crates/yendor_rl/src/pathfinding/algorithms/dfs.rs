@@ -4,18 +4,27 @@ use crate::prelude::*;
 pub struct Dfs;
 
 impl PathAlgorithm for Dfs {
-    fn compute_path<T>(
+    fn compute_path_limited<T>(
         origin: IVec2,
         destination: IVec2,
         provider: &mut impl PathProvider<T>,
         mut pass_through_data: T,
-    ) -> Vec<IVec2> {
+        limits: SearchLimits,
+    ) -> Result<PathResult, PathError> {
+        let mut budget = SearchBudget::new(origin, limits);
         let result = dfs(
             origin,
-            |&p| provider.get_neighbors(p, &mut pass_through_data),
+            |&p| budget.neighbors(p, provider, &mut pass_through_data),
             |&p| p == destination,
         );
 
-        result.map_or_else(Vec::new, |path| path)
+        budget.finish(result.map(|path| {
+            PathResult::from_path(
+                path,
+                budget.nodes_expanded(),
+                provider,
+                &mut pass_through_data,
+            )
+        }))
     }
 }
@@ -4,15 +4,17 @@ use crate::prelude::*;
 pub struct DijkstraPartial;
 
 impl PathAlgorithm for DijkstraPartial {
-    fn compute_path<T>(
+    fn compute_path_limited<T>(
         origin: IVec2,
         destination: IVec2,
         provider: &mut impl PathProvider<T>,
         mut pass_through_data: T,
-    ) -> Vec<IVec2> {
+        limits: SearchLimits,
+    ) -> Result<PathResult, PathError> {
+        let mut budget = SearchBudget::new(origin, limits);
         let (paths, _) = dijkstra_partial(
             &origin,
-            |&p| provider.generate_successors(p, &mut pass_through_data),
+            |&p| budget.successors(p, provider.generate_successors(p, &mut pass_through_data)),
             |&p| p == destination,
         );
 
@@ -26,6 +28,16 @@ impl PathAlgorithm for DijkstraPartial {
             .map(|(pt, _)| pt)
             .unwrap_or(&origin);
 
-        build_path(target, &paths)
+        // nothing but the origin could be reached
+        if *target == origin && origin != destination {
+            return budget.finish(None);
+        }
+
+        let cost = paths.get(target).map_or(0, |(_, cost)| *cost);
+        budget.finish(Some(PathResult::new(
+            build_path(target, &paths),
+            cost,
+            budget.nodes_expanded(),
+        )))
     }
 }
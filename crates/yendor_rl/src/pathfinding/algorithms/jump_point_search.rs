@@ -0,0 +1,307 @@
+use std::collections::BinaryHeap;
+
+use crate::prelude::*;
+
+/// Jump point search algorithm.
+///
+/// A* specialised for uniform-cost, 8-connected grids: straight and diagonal runs are skipped
+/// over ("jumped") and only cells with forced neighbors are expanded.
+/// [Jump Point Search](https://harablog.wordpress.com/2011/09/07/jump-point-search/)
+///
+/// Walls are detected through [`PathProvider::can_move`], so diagonal moves may cut corners or
+/// not, as with every [`CornerCutting`]. Providers refusing a diagonal move between open cells,
+/// such as [`Movement::Cardinal`], are searched with [`AStar`] instead.
+pub struct JumpPointSearch;
+
+impl PathAlgorithm for JumpPointSearch {
+    fn compute_path_limited<T>(
+        origin: IVec2,
+        destination: IVec2,
+        provider: &mut impl PathProvider<T>,
+        mut pass_through_data: T,
+        limits: SearchLimits,
+    ) -> Result<PathResult, PathError> {
+        let mut jumps = Jumps {
+            provider: &*provider,
+            pass_through_data: &mut pass_through_data,
+            destination,
+            cardinal: false,
+        };
+        let result = jumps.search(origin, limits);
+        if jumps.cardinal {
+            return AStar::compute_path_limited(
+                origin,
+                destination,
+                provider,
+                pass_through_data,
+                limits,
+            );
+        }
+        result
+    }
+}
+
+// a single search, remembering whether the provider turned out to only move cardinally
+struct Jumps<'a, P, T> {
+    provider: &'a P,
+    pass_through_data: &'a mut T,
+    destination: IVec2,
+    cardinal: bool,
+}
+
+impl<'a, T, P: PathProvider<T>> Jumps<'a, P, T> {
+    const ALL_DIRECTIONS: [IVec2; 8] = [
+        IVec2::new(0, 1),
+        IVec2::new(1, 0),
+        IVec2::new(0, -1),
+        IVec2::new(-1, 0),
+        IVec2::new(1, 1),
+        IVec2::new(1, -1),
+        IVec2::new(-1, -1),
+        IVec2::new(-1, 1),
+    ];
+
+    fn search(&mut self, origin: IVec2, limits: SearchLimits) -> Result<PathResult, PathError> {
+        let mut nodes_expanded = 0;
+        let mut cost_exceeded = false;
+        let mut open = BinaryHeap::new();
+        let mut costs = HashMap::default();
+        let mut parents = HashMap::default();
+
+        costs.insert(origin, 0);
+        open.push(SearchNode {
+            position: origin,
+            cost: 0,
+            estimate: self.provider.distance(origin, self.destination),
+        });
+
+        while let Some(SearchNode { position, cost, .. }) = open.pop() {
+            if position == self.destination {
+                let path = Self::build_path(self.destination, &parents);
+                return Ok(PathResult::new(path, cost, nodes_expanded));
+            }
+
+            if cost > costs[&position] {
+                continue;
+            }
+
+            if !limits.allows_expansion(nodes_expanded) {
+                return Err(PathError::SearchLimitExceeded);
+            }
+            nodes_expanded += 1;
+
+            let direction = parents
+                .get(&position)
+                .map_or(IVec2::ZERO, |parent: &IVec2| (position - *parent).signum());
+
+            for direction in self.pruned_directions(position, direction) {
+                let Some((jump_point, jump_cost)) = self.jump(position, direction) else {
+                    continue;
+                };
+
+                let next_cost = cost + jump_cost;
+                if !limits.allows_cost(next_cost) {
+                    cost_exceeded = true;
+                    continue;
+                }
+
+                if next_cost < costs.get(&jump_point).copied().unwrap_or(u32::MAX) {
+                    costs.insert(jump_point, next_cost);
+                    parents.insert(jump_point, position);
+                    open.push(SearchNode {
+                        position: jump_point,
+                        cost: next_cost,
+                        estimate: next_cost + self.provider.distance(jump_point, self.destination),
+                    });
+                }
+            }
+
+            // the pruning rules miss paths without diagonal moves, the caller falls back to A*
+            if self.cardinal {
+                return Err(PathError::NoPath);
+            }
+        }
+
+        if cost_exceeded {
+            Err(PathError::SearchLimitExceeded)
+        } else {
+            Err(PathError::NoPath)
+        }
+    }
+
+    // asks the provider, noting diagonal moves refused even though both cells beside the move
+    // and the cell past them are open
+    fn can_move(&mut self, from_position: IVec2, to_position: IVec2) -> bool {
+        if self
+            .provider
+            .can_move(from_position, to_position, self.pass_through_data)
+        {
+            return true;
+        }
+
+        let offset = to_position - from_position;
+        if offset.x != 0 && offset.y != 0 && !self.cardinal {
+            self.cardinal = [IVec2::new(offset.x, 0), IVec2::new(0, offset.y)]
+                .into_iter()
+                .all(|side| {
+                    let side = from_position + side;
+                    self.provider
+                        .can_move(from_position, side, self.pass_through_data)
+                        && self
+                            .provider
+                            .can_move(side, to_position, self.pass_through_data)
+                });
+        }
+        false
+    }
+
+    // the directions worth exploring when arriving at `position` travelling in `direction`
+    fn pruned_directions(&mut self, position: IVec2, direction: IVec2) -> Vec<IVec2> {
+        if direction == IVec2::ZERO {
+            return Self::ALL_DIRECTIONS.to_vec();
+        }
+
+        let mut directions = Vec::with_capacity(7);
+        if direction.x != 0 && direction.y != 0 {
+            directions.push(IVec2::new(direction.x, 0));
+            directions.push(IVec2::new(0, direction.y));
+        }
+        directions.push(direction);
+        for offset in Self::candidates(direction) {
+            if self.is_forced(position, direction, offset) {
+                directions.push(offset);
+            }
+        }
+        directions
+    }
+
+    // walks from `position` in `direction` until a jump point is found, returning the jump point
+    // and the cost of walking there.
+    fn jump(&mut self, mut position: IVec2, direction: IVec2) -> Option<(IVec2, u32)> {
+        let mut cost = 0;
+        loop {
+            let next = position + direction;
+            if !self.can_move(position, next) {
+                return None;
+            }
+
+            cost += self.provider.cost(position, next, self.pass_through_data);
+            position = next;
+
+            if position == self.destination || self.has_forced_neighbor(position, direction) {
+                return Some((position, cost));
+            }
+
+            if direction.x != 0
+                && direction.y != 0
+                && (self.jump(position, IVec2::new(direction.x, 0)).is_some()
+                    || self.jump(position, IVec2::new(0, direction.y)).is_some())
+            {
+                return Some((position, cost));
+            }
+        }
+    }
+
+    fn has_forced_neighbor(&mut self, position: IVec2, direction: IVec2) -> bool {
+        Self::candidates(direction)
+            .into_iter()
+            .any(|offset| self.is_forced(position, direction, offset))
+    }
+
+    // the neighbors which are not natural successors, but may be forced: beside and diagonally
+    // ahead of a straight move, beside and diagonally behind a diagonal move
+    fn candidates(direction: IVec2) -> [IVec2; 4] {
+        if direction.x != 0 && direction.y != 0 {
+            [
+                IVec2::new(-direction.x, 0),
+                IVec2::new(0, -direction.y),
+                IVec2::new(-direction.x, direction.y),
+                IVec2::new(direction.x, -direction.y),
+            ]
+        } else {
+            let side = direction.perp();
+            [side, direction + side, -side, direction - side]
+        }
+    }
+
+    // a neighbor is forced if the parent cannot reach it as quickly without passing `position`,
+    // either directly or around the corner between them
+    fn is_forced(&mut self, position: IVec2, direction: IVec2, offset: IVec2) -> bool {
+        let parent = position - direction;
+        let neighbor = position + offset;
+        let corner = position + (offset - direction).signum();
+        if !self.can_move(position, neighbor) {
+            return false;
+        }
+
+        let direct = parent.distance(neighbor) == 1 && self.can_move(parent, neighbor);
+        let around =
+            corner != parent && self.can_move(parent, corner) && self.can_move(corner, neighbor);
+        !direct && !around
+    }
+
+    // expands the chain of jump points back into every cell along the path
+    fn build_path(destination: IVec2, parents: &HashMap<IVec2, IVec2>) -> Vec<IVec2> {
+        let mut path = vec![destination];
+        let mut current = destination;
+        while let Some(parent) = parents.get(&current) {
+            let step = (*parent - current).signum();
+            while current != *parent {
+                current += step;
+                path.push(current);
+            }
+        }
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(all(test, feature = "random"))]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn matches_astar() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let dimensions = UVec2::new(16, 16);
+        let corner_cuttings = [
+            CornerCutting::Allowed,
+            CornerCutting::NoSqueezing,
+            CornerCutting::Forbidden,
+        ];
+
+        for movement in [Movement::Cardinal, Movement::EightWay] {
+            for corner_cutting in corner_cuttings {
+                for _ in 0..200 {
+                    let cells = (0..dimensions.size()).map(|_| !rng.one_in(4_u32)).collect();
+                    let mut grid = Grid::new(dimensions, cells);
+                    let origin = IVec2::new(rng.max(16_u32) as i32, rng.max(16_u32) as i32);
+                    let destination = IVec2::new(rng.max(16_u32) as i32, rng.max(16_u32) as i32);
+                    grid[origin] = true;
+                    grid[destination] = true;
+
+                    let mut provider = GridPathProvider::new(&grid, |cell: &bool| *cell)
+                        .with_movement(movement)
+                        .with_corner_cutting(corner_cutting);
+                    let astar = PathFinder::Astar.compute(origin, destination, &mut provider, ());
+                    let jps =
+                        PathFinder::JumpPointSearch.compute(origin, destination, &mut provider, ());
+
+                    let case = format!("{movement:?} {corner_cutting:?} {origin} -> {destination}");
+                    match (astar, jps) {
+                        (Ok(astar), Ok(jps)) => {
+                            assert_eq!(astar.cost, jps.cost, "{case}");
+                            assert_eq!(astar.path.len(), jps.path.len(), "{case}");
+                            assert_eq!(jps.path.first(), Some(&origin), "{case}");
+                            assert_eq!(jps.path.last(), Some(&destination), "{case}");
+                            for step in jps.path.windows(2) {
+                                assert!(provider.can_move(step[0], step[1], &mut ()), "{case}");
+                            }
+                        }
+                        (astar, jps) => assert_eq!(astar, jps, "{case}"),
+                    }
+                }
+            }
+        }
+    }
+}
@@ -12,3 +12,5 @@ mod id_astar;
 pub use id_astar::*;
 mod id_dfs;
 pub use id_dfs::*;
+mod jump_point_search;
+pub use jump_point_search::*;
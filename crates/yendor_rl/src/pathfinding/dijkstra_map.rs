@@ -0,0 +1,304 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::prelude::*;
+
+/// A multi-source distance field (a.k.a. "Dijkstra map").
+///
+/// Every cell stores the cheapest cost to reach the nearest goal, so an actor can walk towards
+/// the goals by repeatedly stepping onto its lowest neighbor.
+/// [Dijkstra Maps](http://www.roguebasin.com/index.php/The_Incredible_Power_of_Dijkstra_Maps)
+#[derive(Debug, Clone)]
+pub struct DijkstraMap {
+    values: Grid<f32>,
+    goals: HashMap<IVec2, f32>,
+    pending: Vec<IVec2>,
+    needs_rebuild: bool,
+}
+
+impl DijkstraMap {
+    /// The value of a cell that cannot reach any goal.
+    pub const UNREACHABLE: f32 = f32::INFINITY;
+
+    /// Creates a new, empty map with the given dimensions.
+    pub fn new(dimensions: UVec2) -> Self {
+        Self {
+            values: Grid::new_copy(dimensions, Self::UNREACHABLE),
+            goals: HashMap::default(),
+            pending: Vec::new(),
+            needs_rebuild: false,
+        }
+    }
+
+    /// Returns the dimensions of the map.
+    #[inline]
+    pub fn dimensions(&self) -> UVec2 {
+        self.values.dimensions()
+    }
+
+    /// Returns the underlying grid of values.
+    #[inline]
+    pub const fn values(&self) -> &Grid<f32> {
+        &self.values
+    }
+
+    /// Returns an iterator over all goals and their starting values.
+    pub fn goals(&self) -> impl Iterator<Item = (IVec2, f32)> + '_ {
+        self.goals
+            .iter()
+            .map(|(position, value)| (*position, *value))
+    }
+
+    /// Returns true if the goals have changed since the last update.
+    #[inline]
+    pub const fn is_dirty(&self) -> bool {
+        self.needs_rebuild || !self.pending.is_empty()
+    }
+
+    /// Adds a goal (or changes the starting value of an existing one).
+    ///
+    /// Goals are usually added with a value of `0.0`, lower values make a goal more desirable.
+    pub fn add_goal(&mut self, position: IVec2, value: f32) {
+        if !position.is_valid(self.dimensions()) {
+            return;
+        }
+
+        match self.goals.insert(position, value) {
+            // Raising a goal can only be handled by a full rebuild.
+            Some(previous) if value > previous => self.needs_rebuild = true,
+            _ => self.pending.push(position),
+        }
+    }
+
+    /// Removes a goal.
+    pub fn remove_goal(&mut self, position: IVec2) {
+        if self.goals.remove(&position).is_some() {
+            self.needs_rebuild = true;
+        }
+    }
+
+    /// Removes all goals.
+    pub fn clear_goals(&mut self) {
+        self.goals.clear();
+        self.pending.clear();
+        self.needs_rebuild = true;
+    }
+
+    /// Returns the value at the specified position, or `None` if the position is out of bounds or
+    /// cannot reach any goal.
+    #[inline]
+    pub fn get(&self, position: IVec2) -> Option<f32> {
+        self.values
+            .get(position.as_uvec2())
+            .copied()
+            .filter(|value| value.is_finite())
+    }
+
+    /// Brings the map up to date with the goals.
+    ///
+    /// Added goals are flooded incrementally, removing or raising a goal re-floods the whole map.
+    pub fn update<T>(&mut self, provider: &mut impl PathProvider<T>, mut pass_through_data: T) {
+        if self.needs_rebuild {
+            self.rebuild_internal(provider, &mut pass_through_data);
+        } else if !self.pending.is_empty() {
+            let seeds = std::mem::take(&mut self.pending)
+                .into_iter()
+                .filter_map(|position| self.goals.get(&position).map(|value| (position, *value)))
+                .collect::<Vec<_>>();
+            self.flood(seeds, provider, &mut pass_through_data);
+        }
+    }
+
+    /// Discards all values and re-floods the map from every goal.
+    pub fn rebuild<T>(&mut self, provider: &mut impl PathProvider<T>, mut pass_through_data: T) {
+        self.rebuild_internal(provider, &mut pass_through_data);
+    }
+
+    /// Returns the neighbor of `position` with the lowest value ("rolling downhill").
+    ///
+    /// Returns `None` if no neighbor is lower than the current position.
+    pub fn next_step<T>(
+        &self,
+        position: IVec2,
+        provider: &mut impl PathProvider<T>,
+        mut pass_through_data: T,
+    ) -> Option<IVec2> {
+        let current = self.get(position).unwrap_or(Self::UNREACHABLE);
+        provider
+            .get_neighbors(position, &mut pass_through_data)
+            .into_iter()
+            .filter_map(|neighbor| self.get(neighbor).map(|value| (neighbor, value)))
+            .filter(|(_, value)| *value < current)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(neighbor, _)| neighbor)
+    }
+
+    /// Creates a new map from this one with every reachable value multiplied by `coefficient`,
+    /// then re-flooded.
+    ///
+    /// A negative coefficient (around `-1.2`) produces a "flee" map: following it downhill leads
+    /// away from the goals while still preferring open space over dead ends.
+    pub fn rescaled<T>(
+        &self,
+        coefficient: f32,
+        provider: &mut impl PathProvider<T>,
+        pass_through_data: T,
+    ) -> Self {
+        let mut map = Self::new(self.dimensions());
+        map.goals = self
+            .values
+            .point_iter()
+            .zip(self.values.iter())
+            .filter(|(_, value)| value.is_finite())
+            .map(|(position, value)| (position, value * coefficient))
+            .collect();
+        map.rebuild(provider, pass_through_data);
+        map
+    }
+
+    fn rebuild_internal<T>(&mut self, provider: &impl PathProvider<T>, pass_through_data: &mut T) {
+        self.values
+            .iter_mut()
+            .for_each(|value| *value = Self::UNREACHABLE);
+        self.pending.clear();
+        self.needs_rebuild = false;
+
+        let seeds = self.goals().collect::<Vec<_>>();
+        self.flood(seeds, provider, pass_through_data);
+    }
+
+    fn flood<T>(
+        &mut self,
+        seeds: Vec<(IVec2, f32)>,
+        provider: &impl PathProvider<T>,
+        pass_through_data: &mut T,
+    ) {
+        let dimensions = self.dimensions();
+        let mut open = BinaryHeap::with_capacity(seeds.len());
+
+        for (position, value) in seeds {
+            let current = &mut self.values[position];
+            if value < *current {
+                *current = value;
+                open.push(FloodNode { position, value });
+            }
+        }
+
+        while let Some(FloodNode { position, value }) = open.pop() {
+            if value > self.values[position] {
+                continue;
+            }
+
+            for neighbor in provider.get_neighbors(position, pass_through_data) {
+                if !neighbor.is_valid(dimensions) {
+                    continue;
+                }
+
+                let next = value + provider.cost(position, neighbor, pass_through_data) as f32;
+                let current = &mut self.values[neighbor];
+                if next < *current {
+                    *current = next;
+                    open.push(FloodNode {
+                        position: neighbor,
+                        value: next,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Entry in the flood queue, ordered so the `BinaryHeap` pops the lowest value first.
+struct FloodNode {
+    position: IVec2,
+    value: f32,
+}
+
+impl PartialEq for FloodNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for FloodNode {}
+
+impl PartialOrd for FloodNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FloodNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.value.total_cmp(&self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn grid() -> Grid<bool> {
+        let mut grid = Grid::new_copy(UVec2::new(8, 8), true);
+        for y in 0..7 {
+            grid[IVec2::new(4, y)] = false;
+        }
+        grid
+    }
+
+    #[test]
+    fn flood() {
+        let grid = grid();
+        let mut provider = GridPathProvider::new(&grid, |walkable: &bool| *walkable);
+        let mut map = DijkstraMap::new(UVec2::new(8, 8));
+        map.add_goal(IVec2::new(0, 0), 0.0);
+        map.update(&mut provider, ());
+
+        assert_eq!(map.get(IVec2::new(0, 0)), Some(0.0));
+        assert_eq!(map.get(IVec2::new(3, 3)), Some(3.0));
+        assert_eq!(map.get(IVec2::new(4, 3)), None);
+        // around the wall through the gap at (4, 7)
+        assert_eq!(map.get(IVec2::new(5, 0)), Some(14.0));
+    }
+
+    #[test]
+    fn incremental_matches_rebuild() {
+        let grid = grid();
+        let mut provider = GridPathProvider::new(&grid, |walkable: &bool| *walkable);
+        let mut map = DijkstraMap::new(UVec2::new(8, 8));
+        map.add_goal(IVec2::new(0, 0), 0.0);
+        map.update(&mut provider, ());
+        map.add_goal(IVec2::new(7, 0), 0.0);
+        map.update(&mut provider, ());
+
+        let mut rebuilt = map.clone();
+        rebuilt.rebuild(&mut provider, ());
+        assert_eq!(map.values(), rebuilt.values());
+
+        map.remove_goal(IVec2::new(7, 0));
+        assert!(map.is_dirty());
+        map.update(&mut provider, ());
+        assert_eq!(map.get(IVec2::new(5, 0)), Some(14.0));
+    }
+
+    #[test]
+    fn roll_downhill() {
+        let grid = grid();
+        let mut provider = GridPathProvider::new(&grid, |walkable: &bool| *walkable);
+        let mut map = DijkstraMap::new(UVec2::new(8, 8));
+        map.add_goal(IVec2::new(0, 0), 0.0);
+        map.update(&mut provider, ());
+
+        let mut position = IVec2::new(7, 0);
+        let mut steps = 0;
+        while let Some(next) = map.next_step(position, &mut provider, ()) {
+            position = next;
+            steps += 1;
+        }
+        assert_eq!(position, IVec2::new(0, 0));
+        assert_eq!(steps, 14);
+
+        let flee = map.rescaled(-1.2, &mut provider, ());
+        let next = flee.next_step(IVec2::new(1, 1), &mut provider, ()).unwrap();
+        assert!(map.get(next).unwrap() > 1.0);
+    }
+}
@@ -0,0 +1,246 @@
+use std::marker::PhantomData;
+
+use crate::prelude::*;
+
+/// The directions a [`GridPathProvider`] can move in.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Movement {
+    /// Only the four [`CardinalDirection`]s.
+    Cardinal,
+    /// All eight directions.
+    #[default]
+    EightWay,
+}
+
+/// How a [`GridPathProvider`] treats diagonal moves past blocked cells.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CornerCutting {
+    /// Diagonal moves are allowed regardless of the cells around them.
+    #[default]
+    Allowed,
+    /// Diagonal moves are allowed unless both cells beside the move are blocked.
+    NoSqueezing,
+    /// Diagonal moves are only allowed if both cells beside the move are walkable.
+    Forbidden,
+}
+
+/// A [`PathProvider`] reading walkability straight from a grid.
+///
+/// ```ignore
+/// let provider = GridPathProvider::new(&map, |tile: &Tile| tile.is_floor())
+///     .with_corner_cutting(CornerCutting::Forbidden)
+///     .with_cost(|_, _, tile: &Tile| tile.movement_cost());
+/// let path = PathFinder::Astar.compute(origin, destination, &mut provider, ());
+/// ```
+pub struct GridPathProvider<'a, C, G, W, K = fn(IVec2, IVec2, &C) -> u32> {
+    grid: &'a G,
+    is_walkable: W,
+    cost: K,
+    movement: Movement,
+    corner_cutting: CornerCutting,
+    cell: PhantomData<C>,
+}
+
+impl<'a, C, G: GridLayer<C>, W: Fn(&C) -> bool> GridPathProvider<'a, C, G, W> {
+    /// Creates a new provider moving in all eight directions onto every cell matching the
+    /// predicate, at a cost of `1` per step.
+    pub fn new(grid: &'a G, is_walkable: W) -> Self {
+        Self {
+            grid,
+            is_walkable,
+            cost: |_, _, _| 1,
+            movement: Movement::default(),
+            corner_cutting: CornerCutting::default(),
+            cell: PhantomData,
+        }
+    }
+}
+
+impl<'a, C, G: GridLayer<C>, W: Fn(&C) -> bool, K: Fn(IVec2, IVec2, &C) -> u32>
+    GridPathProvider<'a, C, G, W, K>
+{
+    /// Sets the directions the provider can move in.
+    pub const fn with_movement(mut self, movement: Movement) -> Self {
+        self.movement = movement;
+        self
+    }
+
+    /// Sets how diagonal moves past blocked cells are treated.
+    pub const fn with_corner_cutting(mut self, corner_cutting: CornerCutting) -> Self {
+        self.corner_cutting = corner_cutting;
+        self
+    }
+
+    /// Sets the cost of moving from one cell onto another, given the cell moved onto.
+    pub fn with_cost<K2: Fn(IVec2, IVec2, &C) -> u32>(
+        self,
+        cost: K2,
+    ) -> GridPathProvider<'a, C, G, W, K2> {
+        GridPathProvider {
+            grid: self.grid,
+            is_walkable: self.is_walkable,
+            cost,
+            movement: self.movement,
+            corner_cutting: self.corner_cutting,
+            cell: PhantomData,
+        }
+    }
+
+    /// Returns the grid this provider reads from.
+    #[inline]
+    pub const fn grid(&self) -> &'a G {
+        self.grid
+    }
+
+    /// Returns true if the position is in bounds and its cell matches the predicate.
+    #[inline]
+    pub fn is_walkable(&self, position: IVec2) -> bool {
+        position.is_valid(self.grid.dimensions())
+            && self
+                .grid
+                .get(position.as_uvec2())
+                .map(&self.is_walkable)
+                .unwrap_or(false)
+    }
+
+    fn is_step_allowed(&self, from_position: IVec2, to_position: IVec2) -> bool {
+        let offset = to_position - from_position;
+        if offset == IVec2::ZERO || offset.abs().max_element() > 1 {
+            return false;
+        }
+
+        if offset.x == 0 || offset.y == 0 {
+            return self.is_walkable(to_position);
+        }
+
+        if self.movement == Movement::Cardinal || !self.is_walkable(to_position) {
+            return false;
+        }
+
+        let horizontal = self.is_walkable(from_position + IVec2::new(offset.x, 0));
+        let vertical = self.is_walkable(from_position + IVec2::new(0, offset.y));
+        match self.corner_cutting {
+            CornerCutting::Allowed => true,
+            CornerCutting::NoSqueezing => horizontal || vertical,
+            CornerCutting::Forbidden => horizontal && vertical,
+        }
+    }
+}
+
+impl<'a, T, C, G: GridLayer<C>, W: Fn(&C) -> bool, K: Fn(IVec2, IVec2, &C) -> u32> PathProvider<T>
+    for GridPathProvider<'a, C, G, W, K>
+{
+    fn get_neighbors(&self, position: IVec2, _pass_through_data: &mut T) -> Vec<IVec2> {
+        let directions = match self.movement {
+            Movement::Cardinal => CardinalDirection::all(),
+            Movement::EightWay => Direction::all(),
+        };
+
+        directions
+            .map(|direction| position + direction.coord())
+            .filter(|neighbor| self.is_step_allowed(position, *neighbor))
+            .collect()
+    }
+
+    fn can_move(
+        &self,
+        from_position: IVec2,
+        to_position: IVec2,
+        _pass_through_data: &mut T,
+    ) -> bool {
+        self.is_step_allowed(from_position, to_position)
+    }
+
+    fn cost(&self, from_position: IVec2, to_position: IVec2, _pass_through_data: &mut T) -> u32 {
+        self.grid
+            .get(to_position.as_uvec2())
+            .map_or(u32::MAX, |cell| {
+                (self.cost)(from_position, to_position, cell)
+            })
+    }
+
+    fn distance(&self, origin: IVec2, destination: IVec2) -> u32 {
+        match self.movement {
+            Movement::Cardinal => {
+                let offset = (destination - origin).abs();
+                (offset.x + offset.y) as u32
+            }
+            Movement::EightWay => origin.distance(destination),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    // a wall with a diagonal gap between (2, 1) and (3, 2)
+    fn grid() -> Grid<char> {
+        let rows = ["......", "...#..", "..#...", "......"];
+        let cells = rows.iter().flat_map(|row| row.chars()).collect();
+        Grid::new(UVec2::new(6, 4), cells)
+    }
+
+    #[test]
+    fn movement() {
+        let grid = grid();
+        let mut provider = GridPathProvider::new(&grid, |cell: &char| *cell == '.');
+        assert_eq!(provider.get_neighbors(IVec2::new(0, 0), &mut ()).len(), 3);
+        assert!(provider.can_move(IVec2::new(2, 1), IVec2::new(3, 2), &mut ()));
+
+        let path = PathFinder::Astar
+            .compute(IVec2::new(0, 0), IVec2::new(5, 3), &mut provider, ())
+            .unwrap();
+        assert_eq!(path.cost, 5);
+
+        let mut provider = provider.with_movement(Movement::Cardinal);
+        assert_eq!(provider.get_neighbors(IVec2::new(0, 0), &mut ()).len(), 2);
+        let path = PathFinder::Astar
+            .compute(IVec2::new(0, 0), IVec2::new(5, 3), &mut provider, ())
+            .unwrap();
+        assert_eq!(path.cost, 8);
+    }
+
+    #[test]
+    fn corner_cutting() {
+        let grid = grid();
+        let from = IVec2::new(2, 1);
+        let to = IVec2::new(3, 2);
+        let provider = |corner_cutting| {
+            GridPathProvider::new(&grid, |cell: &char| *cell == '.')
+                .with_corner_cutting(corner_cutting)
+        };
+
+        assert!(provider(CornerCutting::Allowed).can_move(from, to, &mut ()));
+        assert!(!provider(CornerCutting::NoSqueezing).can_move(from, to, &mut ()));
+        assert!(provider(CornerCutting::NoSqueezing).can_move(IVec2::ZERO, IVec2::ONE, &mut ()));
+        assert!(!provider(CornerCutting::Forbidden).can_move(
+            IVec2::new(2, 1),
+            IVec2::new(3, 0),
+            &mut ()
+        ));
+        assert!(provider(CornerCutting::NoSqueezing).can_move(
+            IVec2::new(2, 1),
+            IVec2::new(3, 0),
+            &mut ()
+        ));
+    }
+
+    #[test]
+    fn cost() {
+        let grid = grid();
+        let mut provider = GridPathProvider::new(&grid, |cell: &char| *cell == '.').with_cost(
+            |from: IVec2, to: IVec2, _: &char| {
+                if from.x != to.x && from.y != to.y {
+                    3
+                } else {
+                    2
+                }
+            },
+        );
+        let path = PathFinder::Dijkstra
+            .compute(IVec2::new(0, 0), IVec2::new(1, 2), &mut provider, ())
+            .unwrap();
+        assert_eq!(path.cost, 5);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::prelude::*;
+
+/// A fixed-size chunk of the map and its part of the abstract graph.
+#[derive(Default, Debug, Clone)]
+pub struct Cluster {
+    /// The cluster needs its transitions and edges recomputed.
+    pub dirty: bool,
+    /// Pairs of (inside, outside) cells crossing the east and north borders.
+    pub transitions: Vec<(IVec2, IVec2)>,
+    /// Abstract edges leaving every entrance of this cluster.
+    pub edges: HashMap<IVec2, Vec<(IVec2, u32)>>,
+}
+
+/// A [`PathProvider`] restricting another provider to the bounds of a single cluster.
+pub struct ClusterProvider<'a, P> {
+    provider: &'a P,
+    min: IVec2,
+    max: IVec2,
+}
+
+impl<'a, P> ClusterProvider<'a, P> {
+    pub const fn new(provider: &'a P, bounds: Rectangle) -> Self {
+        Self {
+            provider,
+            min: bounds.min,
+            max: bounds.max,
+        }
+    }
+
+    fn contains(&self, position: IVec2) -> bool {
+        position.cmpge(self.min).all() && position.cmple(self.max).all()
+    }
+}
+
+impl<'a, 'b, T, P: PathProvider<T>> PathProvider<&'b mut T> for ClusterProvider<'a, P> {
+    fn get_neighbors(&self, position: IVec2, pass_through_data: &mut &'b mut T) -> Vec<IVec2> {
+        let mut neighbors = self.provider.get_neighbors(position, pass_through_data);
+        neighbors.retain(|neighbor| self.contains(*neighbor));
+        neighbors
+    }
+
+    fn cost(
+        &self,
+        from_position: IVec2,
+        to_position: IVec2,
+        pass_through_data: &mut &'b mut T,
+    ) -> u32 {
+        self.provider
+            .cost(from_position, to_position, pass_through_data)
+    }
+
+    fn distance(&self, origin: IVec2, destination: IVec2) -> u32 {
+        self.provider.distance(origin, destination)
+    }
+}
@@ -0,0 +1,427 @@
+use crate::prelude::*;
+
+mod cluster;
+use cluster::*;
+
+/// Hierarchical pathfinding (HPA*) for large maps.
+///
+/// The map is partitioned into fixed-size clusters, entrances between neighboring clusters are
+/// connected into an abstract graph, and long distance queries are answered on that graph
+/// before being refined into a full path with local searches.
+/// [HPA*](https://webdocs.cs.ualberta.ca/~mmueller/ps/hpastar.pdf)
+///
+/// Clusters are connected through their cardinal borders, so the resulting paths are near
+/// optimal rather than optimal.
+#[derive(Debug, Clone)]
+pub struct HierarchicalPathFinder {
+    dimensions: UVec2,
+    cluster_size: UVec2,
+    clusters: Grid<Cluster>,
+}
+
+impl HierarchicalPathFinder {
+    /// Borders with an opening longer than this get an entrance at both ends instead of a
+    /// single one in the middle.
+    const MAX_ENTRANCE_WIDTH: usize = 6;
+
+    /// Creates a new pathfinder for a map of the given dimensions, split into square clusters
+    /// of `cluster_size` cells.
+    ///
+    /// The abstract graph is built lazily on the first query.
+    pub fn new(dimensions: UVec2, cluster_size: u32) -> Self {
+        let cluster_size = UVec2::splat(cluster_size.max(1));
+        let cluster_count = (dimensions + cluster_size - UVec2::ONE) / cluster_size;
+        Self {
+            dimensions,
+            cluster_size,
+            clusters: Grid::new_fn(cluster_count, |_| Cluster {
+                dirty: true,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Returns the dimensions of the map.
+    #[inline]
+    pub const fn dimensions(&self) -> UVec2 {
+        self.dimensions
+    }
+
+    /// Returns the number of clusters along each axis.
+    #[inline]
+    pub fn cluster_count(&self) -> UVec2 {
+        self.clusters.dimensions()
+    }
+
+    /// Returns true if any cluster needs to be rebuilt.
+    pub fn is_dirty(&self) -> bool {
+        self.clusters.iter().any(|cluster| cluster.dirty)
+    }
+
+    /// Marks the clusters affected by a change of the cell at `position` for rebuilding.
+    ///
+    /// Call this for every cell changed with [`GridLayer::set`], only the touched clusters (and
+    /// their neighbors when the cell lies on a border) will be recomputed.
+    pub fn invalidate(&mut self, position: IVec2) {
+        if !position.is_valid(self.dimensions) {
+            return;
+        }
+
+        let cluster = self.cluster_of(position);
+        let local = position - cluster * self.cluster_size.as_ivec2();
+        let last = self.cluster_bounds(cluster).max - self.cluster_bounds(cluster).min;
+
+        self.mark_dirty(cluster);
+        if local.x == 0 {
+            self.mark_dirty(cluster - IVec2::X);
+        }
+        if local.x == last.x {
+            self.mark_dirty(cluster + IVec2::X);
+        }
+        if local.y == 0 {
+            self.mark_dirty(cluster - IVec2::Y);
+        }
+        if local.y == last.y {
+            self.mark_dirty(cluster + IVec2::Y);
+        }
+    }
+
+    /// Marks every cluster for rebuilding.
+    pub fn invalidate_all(&mut self) {
+        self.clusters
+            .iter_mut()
+            .for_each(|cluster| cluster.dirty = true);
+    }
+
+    /// Rebuilds the abstract graph of every dirty cluster.
+    pub fn update<T>(&mut self, provider: &mut impl PathProvider<T>, mut pass_through_data: T) {
+        self.update_internal(provider, &mut pass_through_data);
+    }
+
+    /// Computes a path from the origin to the destination.
+    pub fn compute<T>(
+        &mut self,
+        origin: IVec2,
+        destination: IVec2,
+        provider: &mut impl PathProvider<T>,
+        mut pass_through_data: T,
+    ) -> Result<PathResult, PathError> {
+        self.update_internal(provider, &mut pass_through_data);
+
+        if !origin.is_valid(self.dimensions) || !destination.is_valid(self.dimensions) {
+            return Err(PathError::NoPath);
+        }
+
+        let origin_cluster = self.cluster_of(origin);
+        let destination_cluster = self.cluster_of(destination);
+
+        if origin_cluster == destination_cluster {
+            let result = self.local_path(origin, destination, provider, &mut pass_through_data);
+            if result.is_ok() {
+                return result;
+            }
+        }
+
+        // temporarily connect the origin and destination to the entrances of their clusters
+        let origin_edges = self
+            .entrances(origin_cluster)
+            .filter_map(|entrance| {
+                self.local_cost(origin, entrance, provider, &mut pass_through_data)
+                    .map(|cost| (entrance, cost))
+            })
+            .collect::<Vec<_>>();
+        let destination_edges = self
+            .entrances(destination_cluster)
+            .filter_map(|entrance| {
+                self.local_cost(entrance, destination, provider, &mut pass_through_data)
+                    .map(|cost| (entrance, cost))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut nodes_expanded = 0;
+        let abstract_path = astar(
+            &origin,
+            |&position| {
+                nodes_expanded += 1;
+                let mut successors = self.edges(position).to_vec();
+                if position == origin {
+                    successors.extend(origin_edges.iter().copied());
+                }
+                if let Some(cost) = destination_edges.get(&position) {
+                    successors.push((destination, *cost));
+                }
+                successors
+            },
+            |&position| provider.distance(position, destination),
+            |&position| position == destination,
+        );
+
+        let Some((abstract_path, cost)) = abstract_path else {
+            return Err(PathError::NoPath);
+        };
+
+        let mut path = vec![origin];
+        for step in abstract_path.windows(2) {
+            if self.cluster_of(step[0]) == self.cluster_of(step[1]) {
+                let local = self.local_path(step[0], step[1], provider, &mut pass_through_data)?;
+                nodes_expanded += local.nodes_expanded;
+                path.extend(local.path.into_iter().skip(1));
+            } else {
+                path.push(step[1]);
+            }
+        }
+
+        Ok(PathResult::new(path, cost, nodes_expanded))
+    }
+
+    fn update_internal<T>(&mut self, provider: &impl PathProvider<T>, pass_through_data: &mut T) {
+        let dirty = self
+            .clusters
+            .point_iter()
+            .filter(|cluster| self.clusters[*cluster].dirty)
+            .collect::<Vec<_>>();
+
+        // transitions must all be up to date before any entrances are connected
+        for cluster in dirty.iter().copied() {
+            self.clusters[cluster].transitions =
+                self.find_transitions(cluster, provider, pass_through_data);
+        }
+
+        for cluster in dirty {
+            self.clusters[cluster].edges = self.build_edges(cluster, provider, pass_through_data);
+            self.clusters[cluster].dirty = false;
+        }
+    }
+
+    // finds the crossings between this cluster and its east and north neighbors
+    fn find_transitions<T>(
+        &self,
+        cluster: IVec2,
+        provider: &impl PathProvider<T>,
+        pass_through_data: &mut T,
+    ) -> Vec<(IVec2, IVec2)> {
+        let bounds = self.cluster_bounds(cluster);
+        let mut transitions = Vec::new();
+
+        let borders = [
+            (
+                IVec2::X,
+                (bounds.min.y..=bounds.max.y)
+                    .map(|y| IVec2::new(bounds.max.x, y))
+                    .collect::<Vec<_>>(),
+            ),
+            (
+                IVec2::Y,
+                (bounds.min.x..=bounds.max.x)
+                    .map(|x| IVec2::new(x, bounds.max.y))
+                    .collect::<Vec<_>>(),
+            ),
+        ];
+
+        for (offset, border) in borders {
+            if !(border[0] + offset).is_valid(self.dimensions) {
+                continue;
+            }
+
+            let mut run = Vec::new();
+            for inside in border {
+                let outside = inside + offset;
+                if provider.can_move(inside, outside, pass_through_data)
+                    && provider.can_move(outside, inside, pass_through_data)
+                {
+                    run.push((inside, outside));
+                } else {
+                    Self::add_entrances(&mut transitions, &mut run);
+                }
+            }
+            Self::add_entrances(&mut transitions, &mut run);
+        }
+
+        transitions
+    }
+
+    fn add_entrances(transitions: &mut Vec<(IVec2, IVec2)>, run: &mut Vec<(IVec2, IVec2)>) {
+        match run.len() {
+            0 => {}
+            len if len < Self::MAX_ENTRANCE_WIDTH => transitions.push(run[len / 2]),
+            len => {
+                transitions.push(run[0]);
+                transitions.push(run[len - 1]);
+            }
+        }
+        run.clear();
+    }
+
+    // connects every entrance of the cluster to each other and to the neighboring clusters
+    fn build_edges<T>(
+        &self,
+        cluster: IVec2,
+        provider: &impl PathProvider<T>,
+        pass_through_data: &mut T,
+    ) -> HashMap<IVec2, Vec<(IVec2, u32)>> {
+        let bounds = self.cluster_bounds(cluster);
+        let mut edges: HashMap<IVec2, Vec<(IVec2, u32)>> = HashMap::default();
+
+        let mut crossings = self.clusters[cluster].transitions.clone();
+        for neighbor in [cluster - IVec2::X, cluster - IVec2::Y] {
+            if let Some(neighbor) = self.clusters.get(neighbor.as_uvec2()) {
+                crossings.extend(
+                    neighbor
+                        .transitions
+                        .iter()
+                        .filter(|(_, outside)| Self::contains(bounds, *outside))
+                        .map(|(inside, outside)| (*outside, *inside)),
+                );
+            }
+        }
+
+        for (inside, outside) in crossings.iter().copied() {
+            let cost = provider.cost(inside, outside, pass_through_data);
+            edges.entry(inside).or_default().push((outside, cost));
+        }
+
+        let entrances = edges.keys().copied().collect::<Vec<_>>();
+        for from in entrances.iter().copied() {
+            for to in entrances.iter().copied().filter(|to| *to != from) {
+                if let Some(cost) = self.local_cost(from, to, provider, pass_through_data) {
+                    edges.entry(from).or_default().push((to, cost));
+                }
+            }
+        }
+
+        edges
+    }
+
+    // a path between two cells, never leaving the cluster of `from`
+    fn local_path<T>(
+        &self,
+        from: IVec2,
+        to: IVec2,
+        provider: &impl PathProvider<T>,
+        pass_through_data: &mut T,
+    ) -> Result<PathResult, PathError> {
+        let bounds = self.cluster_bounds(self.cluster_of(from));
+        let mut cluster_provider = ClusterProvider::new(provider, bounds);
+        AStar::compute_path(from, to, &mut cluster_provider, pass_through_data)
+    }
+
+    fn local_cost<T>(
+        &self,
+        from: IVec2,
+        to: IVec2,
+        provider: &impl PathProvider<T>,
+        pass_through_data: &mut T,
+    ) -> Option<u32> {
+        self.local_path(from, to, provider, pass_through_data)
+            .ok()
+            .map(|result| result.cost)
+    }
+
+    fn entrances(&self, cluster: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        self.clusters[cluster].edges.keys().copied()
+    }
+
+    fn edges(&self, position: IVec2) -> &[(IVec2, u32)] {
+        self.clusters[self.cluster_of(position)]
+            .edges
+            .get(&position)
+            .map_or(&[], |edges| edges.as_slice())
+    }
+
+    fn mark_dirty(&mut self, cluster: IVec2) {
+        if let Some(cluster) = self.clusters.get_mut(cluster.as_uvec2()) {
+            cluster.dirty = true;
+        }
+    }
+
+    fn cluster_of(&self, position: IVec2) -> IVec2 {
+        position / self.cluster_size.as_ivec2()
+    }
+
+    fn cluster_bounds(&self, cluster: IVec2) -> Rectangle {
+        let min = cluster * self.cluster_size.as_ivec2();
+        let max = (min + self.cluster_size.as_ivec2()).min(self.dimensions.as_ivec2()) - 1;
+        Rectangle::new(min, max)
+    }
+
+    fn contains(bounds: Rectangle, position: IVec2) -> bool {
+        position.cmpge(bounds.min).all() && position.cmple(bounds.max).all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    // vertical walls every 10 cells, with a single gap alternating between top and bottom
+    fn grid() -> Grid<bool> {
+        let dimensions = UVec2::new(40, 40);
+        let mut grid = Grid::new_copy(dimensions, true);
+        for x in [10, 20, 30] {
+            let gap = if x == 20 { 1 } else { 38 };
+            for y in 0..40 {
+                grid[IVec2::new(x, y)] = y == gap;
+            }
+        }
+        grid
+    }
+
+    fn assert_valid_path(
+        path: &[IVec2],
+        origin: IVec2,
+        destination: IVec2,
+        provider: &impl PathProvider<()>,
+    ) {
+        assert_eq!(path.first(), Some(&origin));
+        assert_eq!(path.last(), Some(&destination));
+        for step in path.windows(2) {
+            assert!(provider.can_move(step[0], step[1], &mut ()));
+        }
+    }
+
+    #[test]
+    fn long_distance() {
+        let grid = grid();
+        let mut provider = GridPathProvider::new(&grid, |walkable: &bool| *walkable);
+        let mut hpa = HierarchicalPathFinder::new(grid.dimensions(), 8);
+        let origin = IVec2::new(0, 0);
+        let destination = IVec2::new(39, 0);
+
+        let result = hpa.compute(origin, destination, &mut provider, ()).unwrap();
+        assert_valid_path(&result.path, origin, destination, &provider);
+
+        let optimal = PathFinder::Astar
+            .compute(origin, destination, &mut provider, ())
+            .unwrap();
+        assert!(result.cost >= optimal.cost);
+        assert!(result.cost <= optimal.cost + optimal.cost / 5);
+    }
+
+    #[test]
+    fn invalidate() {
+        let mut grid = grid();
+        let mut hpa = HierarchicalPathFinder::new(grid.dimensions(), 8);
+        let origin = IVec2::new(0, 0);
+        let destination = IVec2::new(39, 0);
+        let mut provider = GridPathProvider::new(&grid, |walkable: &bool| *walkable);
+        assert!(hpa.compute(origin, destination, &mut provider, ()).is_ok());
+        assert!(!hpa.is_dirty());
+
+        // close the gap in the middle wall
+        let gap = IVec2::new(20, 1);
+        grid[gap] = false;
+        hpa.invalidate(gap);
+        assert!(hpa.is_dirty());
+        let mut provider = GridPathProvider::new(&grid, |walkable: &bool| *walkable);
+        assert_eq!(
+            hpa.compute(origin, destination, &mut provider, ()),
+            Err(PathError::NoPath)
+        );
+
+        grid[gap] = true;
+        hpa.invalidate(gap);
+        let mut provider = GridPathProvider::new(&grid, |walkable: &bool| *walkable);
+        let result = hpa.compute(origin, destination, &mut provider, ()).unwrap();
+        assert_valid_path(&result.path, origin, destination, &provider);
+    }
+}
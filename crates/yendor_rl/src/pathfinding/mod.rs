@@ -1,9 +1,25 @@
 //! Provides an api for dealing with 2D grid-based pathfinding.
 mod algorithms;
 pub use algorithms::*;
+mod dijkstra_map;
+pub use dijkstra_map::*;
+mod grid_path_provider;
+pub use grid_path_provider::*;
+mod hierarchical;
+pub use hierarchical::*;
 mod path_algorithm;
 pub use path_algorithm::*;
 mod path_provider;
 pub use path_provider::*;
+mod path_result;
+pub use path_result::*;
+mod path_search;
+pub use path_search::*;
 mod pathfinder;
 pub use pathfinder::*;
+mod search_budget;
+pub(crate) use search_budget::*;
+mod search_limits;
+pub use search_limits::*;
+mod search_node;
+pub(crate) use search_node::*;
@@ -3,10 +3,29 @@ use crate::prelude::*;
 /// Trait to implement 2D path algorithms.
 pub trait PathAlgorithm {
     /// Provides a 2D path between points, using the specified algorithm.
+    ///
+    /// If the origin is the destination, the path only contains the origin.
     fn compute_path<T>(
         origin: IVec2,
         destination: IVec2,
         provider: &mut impl PathProvider<T>,
         pass_through_data: T,
-    ) -> Vec<IVec2>;
+    ) -> Result<PathResult, PathError> {
+        Self::compute_path_limited(
+            origin,
+            destination,
+            provider,
+            pass_through_data,
+            SearchLimits::UNLIMITED,
+        )
+    }
+
+    /// Provides a 2D path between points, giving up once the search exceeds the limits.
+    fn compute_path_limited<T>(
+        origin: IVec2,
+        destination: IVec2,
+        provider: &mut impl PathProvider<T>,
+        pass_through_data: T,
+        limits: SearchLimits,
+    ) -> Result<PathResult, PathError>;
 }
@@ -5,6 +5,17 @@ pub trait PathProvider<T> {
     /// Returns the neighbors of the specified position.
     fn get_neighbors(&self, position: IVec2, pass_through_data: &mut T) -> Vec<IVec2>;
 
+    /// Returns true if it is possible to move directly from the origin to the destination.
+    fn can_move(
+        &self,
+        from_position: IVec2,
+        to_position: IVec2,
+        pass_through_data: &mut T,
+    ) -> bool {
+        self.get_neighbors(from_position, pass_through_data)
+            .contains(&to_position)
+    }
+
     /// Returns the cost of moving from the origin to the destination.
     fn cost(&self, _from_position: IVec2, _to_position: IVec2, _pass_through_data: &mut T) -> u32 {
         1
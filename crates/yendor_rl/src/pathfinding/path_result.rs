@@ -0,0 +1,132 @@
+use crate::prelude::*;
+
+/// The outcome of a successful path search.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct PathResult {
+    /// Every position along the path, starting at the origin.
+    pub path: Vec<IVec2>,
+    /// The total cost of walking the path.
+    pub cost: u32,
+    /// The number of nodes whose successors were generated during the search.
+    pub nodes_expanded: usize,
+}
+
+impl PathResult {
+    /// Creates a new result.
+    pub const fn new(path: Vec<IVec2>, cost: u32, nodes_expanded: usize) -> Self {
+        Self {
+            path,
+            cost,
+            nodes_expanded,
+        }
+    }
+
+    /// Creates a new result, summing the cost of every step of the path with the provider.
+    pub fn from_path<T>(
+        path: Vec<IVec2>,
+        nodes_expanded: usize,
+        provider: &impl PathProvider<T>,
+        pass_through_data: &mut T,
+    ) -> Self {
+        let cost = path
+            .windows(2)
+            .map(|step| provider.cost(step[0], step[1], pass_through_data))
+            .sum();
+        Self::new(path, cost, nodes_expanded)
+    }
+
+    /// Returns the last position of the path.
+    #[inline]
+    pub fn destination(&self) -> Option<IVec2> {
+        self.path.last().copied()
+    }
+}
+
+/// The reasons a path search can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PathError {
+    /// The destination cannot be reached from the origin.
+    NoPath,
+    /// The search gave up before reaching the destination.
+    SearchLimitExceeded,
+    /// The search was abandoned before it completed.
+    Cancelled,
+}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoPath => write!(f, "no path to the destination"),
+            Self::SearchLimitExceeded => write!(f, "search limit exceeded"),
+            Self::Cancelled => write!(f, "search cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    const ALGORITHMS: [PathFinder; 8] = [
+        PathFinder::Bfs,
+        PathFinder::Dfs,
+        PathFinder::IDDfs,
+        PathFinder::Astar,
+        PathFinder::IDAstar,
+        PathFinder::Dijkstra,
+        PathFinder::DijkstraPartial,
+        PathFinder::JumpPointSearch,
+    ];
+
+    // a 5x5 room with the cell at (4, 4) walled off
+    struct Provider;
+    impl PathProvider<()> for Provider {
+        fn get_neighbors(&self, position: IVec2, _pass_through_data: &mut ()) -> Vec<IVec2> {
+            Direction::all()
+                .map(|direction| position + direction.coord())
+                .filter(|p| p.is_valid(UVec2::new(5, 5)) && *p != IVec2::new(4, 4))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn origin_is_destination() {
+        for algorithm in ALGORITHMS {
+            let origin = IVec2::new(2, 2);
+            let result = algorithm
+                .compute(origin, origin, &mut Provider, ())
+                .unwrap();
+            assert_eq!(result.path, vec![origin]);
+            assert_eq!(result.cost, 0);
+        }
+    }
+
+    #[test]
+    fn cost() {
+        for algorithm in ALGORITHMS {
+            let result = algorithm
+                .compute(IVec2::new(0, 0), IVec2::new(3, 0), &mut Provider, ())
+                .unwrap();
+            assert_eq!(result.cost as usize, result.path.len() - 1);
+            assert!(result.nodes_expanded > 0);
+        }
+    }
+
+    #[test]
+    fn no_path() {
+        // iterative deepening never gives up and partial paths always succeed
+        let algorithms = [
+            PathFinder::Bfs,
+            PathFinder::Dfs,
+            PathFinder::Astar,
+            PathFinder::Dijkstra,
+            PathFinder::JumpPointSearch,
+        ];
+        for algorithm in algorithms {
+            let result = algorithm.compute(IVec2::new(0, 0), IVec2::new(4, 4), &mut Provider, ());
+            assert_eq!(result, Err(PathError::NoPath));
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::BinaryHeap;
+
+use crate::prelude::*;
+
+/// The progress of a [`PathSearch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchStatus {
+    /// The search needs more steps.
+    InProgress,
+    /// The search has finished, successfully or not.
+    Complete(Result<PathResult, PathError>),
+}
+
+/// A resumable A* search.
+///
+/// Instead of blocking until a path is found, the search is advanced a few expansions at a time
+/// with [`PathSearch::step`], so long searches can be spread across several frames. The search
+/// can be attached to an entity and stepped from a system.
+#[derive(Component, Debug, Clone)]
+pub struct PathSearch {
+    origin: IVec2,
+    destination: IVec2,
+    limits: SearchLimits,
+    open: BinaryHeap<SearchNode>,
+    costs: HashMap<IVec2, u32>,
+    parents: HashMap<IVec2, IVec2>,
+    nodes_expanded: usize,
+    cost_exceeded: bool,
+    status: SearchStatus,
+}
+
+impl PathSearch {
+    /// Creates a new search from the origin to the destination.
+    pub fn new(origin: IVec2, destination: IVec2) -> Self {
+        Self::with_limits(origin, destination, SearchLimits::UNLIMITED)
+    }
+
+    /// Creates a new search from the origin to the destination, giving up once the search
+    /// exceeds the limits.
+    pub fn with_limits(origin: IVec2, destination: IVec2, limits: SearchLimits) -> Self {
+        let mut costs = HashMap::default();
+        costs.insert(origin, 0);
+
+        let mut open = BinaryHeap::new();
+        open.push(SearchNode {
+            position: origin,
+            cost: 0,
+            estimate: 0,
+        });
+
+        Self {
+            origin,
+            destination,
+            limits,
+            open,
+            costs,
+            parents: HashMap::default(),
+            nodes_expanded: 0,
+            cost_exceeded: false,
+            status: SearchStatus::InProgress,
+        }
+    }
+
+    /// Returns the origin of the search.
+    #[inline]
+    pub const fn origin(&self) -> IVec2 {
+        self.origin
+    }
+
+    /// Returns the destination of the search.
+    #[inline]
+    pub const fn destination(&self) -> IVec2 {
+        self.destination
+    }
+
+    /// Returns the number of nodes expanded so far.
+    #[inline]
+    pub const fn nodes_expanded(&self) -> usize {
+        self.nodes_expanded
+    }
+
+    /// Returns the progress of the search.
+    #[inline]
+    pub const fn status(&self) -> &SearchStatus {
+        &self.status
+    }
+
+    /// Returns true if the search has finished.
+    #[inline]
+    pub const fn is_complete(&self) -> bool {
+        matches!(self.status, SearchStatus::Complete(_))
+    }
+
+    /// Abandons the search, it completes with [`PathError::Cancelled`].
+    pub fn cancel(&mut self) {
+        if !self.is_complete() {
+            self.complete(Err(PathError::Cancelled));
+        }
+    }
+
+    /// Advances the search by up to `max_expansions` expansions.
+    pub fn step<T>(
+        &mut self,
+        max_expansions: usize,
+        provider: &mut impl PathProvider<T>,
+        mut pass_through_data: T,
+    ) -> &SearchStatus {
+        let mut expansions = 0;
+        while expansions < max_expansions && !self.is_complete() {
+            let Some(SearchNode { position, cost, .. }) = self.open.pop() else {
+                if self.cost_exceeded {
+                    self.complete(Err(PathError::SearchLimitExceeded));
+                } else {
+                    self.complete(Err(PathError::NoPath));
+                }
+                break;
+            };
+
+            if position == self.destination {
+                let result = PathResult::new(self.build_path(), cost, self.nodes_expanded);
+                self.complete(Ok(result));
+                break;
+            }
+
+            if cost > self.costs[&position] {
+                continue;
+            }
+
+            if !self.limits.allows_expansion(self.nodes_expanded) {
+                self.complete(Err(PathError::SearchLimitExceeded));
+                break;
+            }
+            self.nodes_expanded += 1;
+            expansions += 1;
+
+            for (next, step_cost) in provider.generate_successors(position, &mut pass_through_data)
+            {
+                let next_cost = cost + step_cost;
+                if !self.limits.allows_cost(next_cost) {
+                    self.cost_exceeded = true;
+                    continue;
+                }
+
+                if next_cost < self.costs.get(&next).copied().unwrap_or(u32::MAX) {
+                    self.costs.insert(next, next_cost);
+                    self.parents.insert(next, position);
+                    self.open.push(SearchNode {
+                        position: next,
+                        cost: next_cost,
+                        estimate: next_cost + provider.distance(next, self.destination),
+                    });
+                }
+            }
+        }
+
+        &self.status
+    }
+
+    /// Runs the search until it completes.
+    pub fn finish<T>(
+        &mut self,
+        provider: &mut impl PathProvider<T>,
+        pass_through_data: T,
+    ) -> &SearchStatus {
+        self.step(usize::MAX, provider, pass_through_data)
+    }
+
+    fn complete(&mut self, result: Result<PathResult, PathError>) {
+        self.open.clear();
+        self.costs.clear();
+        self.parents.clear();
+        self.status = SearchStatus::Complete(result);
+    }
+
+    fn build_path(&self) -> Vec<IVec2> {
+        let mut path = vec![self.destination];
+        let mut current = self.destination;
+        while let Some(parent) = self.parents.get(&current) {
+            path.push(*parent);
+            current = *parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    // a 16x16 room split by a wall with a gap at the top
+    fn grid() -> Grid<bool> {
+        let mut grid = Grid::new_copy(UVec2::new(16, 16), true);
+        for y in 0..15 {
+            grid[IVec2::new(8, y)] = false;
+        }
+        grid
+    }
+
+    #[test]
+    fn time_sliced() {
+        let grid = grid();
+        let mut provider = GridPathProvider::new(&grid, |walkable: &bool| *walkable);
+        let origin = IVec2::new(0, 0);
+        let destination = IVec2::new(15, 0);
+
+        let mut search = PathSearch::new(origin, destination);
+        let mut steps = 0;
+        while search.step(4, &mut provider, ()) == &SearchStatus::InProgress {
+            steps += 1;
+        }
+        assert!(steps > 1);
+
+        let expected = PathFinder::Astar.compute(origin, destination, &mut provider, ());
+        let SearchStatus::Complete(result) = search.status() else {
+            unreachable!()
+        };
+        assert_eq!(
+            result.as_ref().map(|result| result.cost),
+            expected.as_ref().map(|result| result.cost)
+        );
+    }
+
+    #[test]
+    fn cancel() {
+        let grid = grid();
+        let mut provider = GridPathProvider::new(&grid, |walkable: &bool| *walkable);
+        let mut search = PathSearch::new(IVec2::new(0, 0), IVec2::new(15, 0));
+        search.step(1, &mut provider, ());
+        search.cancel();
+        assert_eq!(
+            search.finish(&mut provider, ()),
+            &SearchStatus::Complete(Err(PathError::Cancelled))
+        );
+    }
+
+    #[test]
+    fn limits() {
+        let grid = grid();
+        let mut provider = GridPathProvider::new(&grid, |walkable: &bool| *walkable);
+        let origin = IVec2::new(0, 0);
+        let destination = IVec2::new(15, 0);
+
+        // the path around the wall costs 30
+        for limits in [
+            SearchLimits::UNLIMITED.with_max_cost(20),
+            SearchLimits::UNLIMITED.with_max_expansions(10),
+        ] {
+            let mut search = PathSearch::with_limits(origin, destination, limits);
+            assert_eq!(
+                search.finish(&mut provider, ()),
+                &SearchStatus::Complete(Err(PathError::SearchLimitExceeded))
+            );
+
+            for algorithm in [PathFinder::Astar, PathFinder::Bfs, PathFinder::Dijkstra] {
+                assert_eq!(
+                    algorithm.compute_limited(origin, destination, &mut provider, (), limits),
+                    Err(PathError::SearchLimitExceeded)
+                );
+            }
+        }
+
+        let limits = SearchLimits::UNLIMITED.with_max_cost(30);
+        let result =
+            PathFinder::Astar.compute_limited(origin, destination, &mut provider, (), limits);
+        assert_eq!(result.map(|result| result.cost), Ok(30));
+    }
+}
@@ -16,6 +16,8 @@ pub enum PathFinder {
     Dijkstra,
     /// Dijkstra algorithm that provides partial paths to destination.
     DijkstraPartial,
+    /// Jump point search algorithm for uniform-cost 8-connected grids.
+    JumpPointSearch,
 }
 
 impl PathFinder {
@@ -26,21 +28,75 @@ impl PathFinder {
         destination: IVec2,
         provider: &mut impl PathProvider<T>,
         pass_through_data: T,
-    ) -> Vec<IVec2> {
+    ) -> Result<PathResult, PathError> {
+        self.compute_limited(
+            origin,
+            destination,
+            provider,
+            pass_through_data,
+            SearchLimits::UNLIMITED,
+        )
+    }
+
+    /// Computes a path from the origin to the destination using the specified algorithm,
+    /// giving up once the search exceeds the limits.
+    pub fn compute_limited<T>(
+        &self,
+        origin: IVec2,
+        destination: IVec2,
+        provider: &mut impl PathProvider<T>,
+        pass_through_data: T,
+        limits: SearchLimits,
+    ) -> Result<PathResult, PathError> {
         match self {
-            Self::Astar => AStar::compute_path(origin, destination, provider, pass_through_data),
-            Self::Bfs => Bfs::compute_path(origin, destination, provider, pass_through_data),
-            Self::Dfs => Dfs::compute_path(origin, destination, provider, pass_through_data),
-            Self::Dijkstra => {
-                Dijkstra::compute_path(origin, destination, provider, pass_through_data)
-            }
-            Self::DijkstraPartial => {
-                DijkstraPartial::compute_path(origin, destination, provider, pass_through_data)
+            Self::Astar => AStar::compute_path_limited(
+                origin,
+                destination,
+                provider,
+                pass_through_data,
+                limits,
+            ),
+            Self::Bfs => {
+                Bfs::compute_path_limited(origin, destination, provider, pass_through_data, limits)
             }
-            Self::IDAstar => {
-                IDAstar::compute_path(origin, destination, provider, pass_through_data)
+            Self::Dfs => {
+                Dfs::compute_path_limited(origin, destination, provider, pass_through_data, limits)
             }
-            Self::IDDfs => IDDfs::compute_path(origin, destination, provider, pass_through_data),
+            Self::Dijkstra => Dijkstra::compute_path_limited(
+                origin,
+                destination,
+                provider,
+                pass_through_data,
+                limits,
+            ),
+            Self::DijkstraPartial => DijkstraPartial::compute_path_limited(
+                origin,
+                destination,
+                provider,
+                pass_through_data,
+                limits,
+            ),
+            Self::IDAstar => IDAstar::compute_path_limited(
+                origin,
+                destination,
+                provider,
+                pass_through_data,
+                limits,
+            ),
+            Self::IDDfs => IDDfs::compute_path_limited(
+                origin,
+                destination,
+                provider,
+                pass_through_data,
+                limits,
+            ),
+            Self::JumpPointSearch => JumpPointSearch::compute_path_limited(
+                origin,
+                destination,
+                provider,
+                pass_through_data,
+                limits,
+            ),
         }
     }
 }
@@ -0,0 +1,91 @@
+use crate::prelude::*;
+
+/// Enforces [`SearchLimits`] from inside the successor function of a search.
+///
+/// The cheapest known cost of every generated node is tracked, so successors past the maximum
+/// cost are cut off as they are generated. This is exact for best-first searches and
+/// conservative for the others, which [`SearchBudget::finish`] corrects for.
+pub struct SearchBudget {
+    limits: SearchLimits,
+    costs: HashMap<IVec2, u32>,
+    nodes_expanded: usize,
+    exceeded: bool,
+}
+
+impl SearchBudget {
+    pub fn new(origin: IVec2, limits: SearchLimits) -> Self {
+        let mut costs = HashMap::default();
+        costs.insert(origin, 0);
+        Self {
+            limits,
+            costs,
+            nodes_expanded: 0,
+            exceeded: false,
+        }
+    }
+
+    #[inline]
+    pub const fn nodes_expanded(&self) -> usize {
+        self.nodes_expanded
+    }
+
+    /// Expands a node, returning the successors that are within the limits.
+    pub fn successors(
+        &mut self,
+        position: IVec2,
+        successors: Vec<(IVec2, u32)>,
+    ) -> Vec<(IVec2, u32)> {
+        if !self.limits.allows_expansion(self.nodes_expanded) {
+            self.exceeded = true;
+            return Vec::new();
+        }
+
+        self.nodes_expanded += 1;
+        let cost = self.costs.get(&position).copied().unwrap_or_default();
+
+        successors
+            .into_iter()
+            .filter(|(next, step_cost)| {
+                let next_cost = cost + step_cost;
+                if !self.limits.allows_cost(next_cost) {
+                    self.exceeded = true;
+                    return false;
+                }
+
+                let best = self.costs.entry(*next).or_insert(next_cost);
+                *best = next_cost.min(*best);
+                true
+            })
+            .collect()
+    }
+
+    /// Expands a node of an unweighted search, returning the neighbors that are within the
+    /// limits.
+    pub fn neighbors<T>(
+        &mut self,
+        position: IVec2,
+        provider: &impl PathProvider<T>,
+        pass_through_data: &mut T,
+    ) -> Vec<IVec2> {
+        let successors = provider
+            .get_neighbors(position, pass_through_data)
+            .into_iter()
+            .map(|next| (next, provider.cost(position, next, pass_through_data)))
+            .collect();
+
+        self.successors(position, successors)
+            .into_iter()
+            .map(|(next, _)| next)
+            .collect()
+    }
+
+    /// Converts the outcome of the search into a result.
+    pub fn finish(&self, result: Option<PathResult>) -> Result<PathResult, PathError> {
+        match result {
+            Some(result) if self.limits.allows_cost(result.cost) => Ok(result),
+            Some(_) => Err(PathError::SearchLimitExceeded),
+            None if self.exceeded => Err(PathError::SearchLimitExceeded),
+            None => Err(PathError::NoPath),
+        }
+    }
+}
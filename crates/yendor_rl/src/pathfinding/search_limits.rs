@@ -0,0 +1,49 @@
+use crate::prelude::*;
+
+/// Limits placed on a single path search.
+///
+/// A search exceeding any of its limits gives up with [`PathError::SearchLimitExceeded`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SearchLimits {
+    /// The maximum number of nodes to expand.
+    pub max_expansions: Option<usize>,
+    /// The maximum total cost of the path.
+    pub max_cost: Option<u32>,
+}
+
+impl SearchLimits {
+    /// No limits, the search runs until it finds a path or runs out of nodes.
+    pub const UNLIMITED: Self = Self::new(None, None);
+
+    /// Creates new limits.
+    pub const fn new(max_expansions: Option<usize>, max_cost: Option<u32>) -> Self {
+        Self {
+            max_expansions,
+            max_cost,
+        }
+    }
+
+    /// Returns a copy of these limits with the given maximum number of expansions.
+    pub const fn with_max_expansions(mut self, max_expansions: usize) -> Self {
+        self.max_expansions = Some(max_expansions);
+        self
+    }
+
+    /// Returns a copy of these limits with the given maximum path cost.
+    pub const fn with_max_cost(mut self, max_cost: u32) -> Self {
+        self.max_cost = Some(max_cost);
+        self
+    }
+
+    /// Returns true if a path of the given cost is within the limits.
+    #[inline]
+    pub const fn allows_cost(&self, cost: u32) -> bool {
+        !matches!(self.max_cost, Some(max_cost) if cost > max_cost)
+    }
+
+    /// Returns true if another node can be expanded after `nodes_expanded` expansions.
+    #[inline]
+    pub const fn allows_expansion(&self, nodes_expanded: usize) -> bool {
+        !matches!(self.max_expansions, Some(max_expansions) if nodes_expanded >= max_expansions)
+    }
+}
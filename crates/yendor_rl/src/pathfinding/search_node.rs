@@ -0,0 +1,27 @@
+use std::cmp::Ordering;
+
+use crate::prelude::*;
+
+/// Entry in the open list of a search, ordered so the `BinaryHeap` pops the lowest estimate
+/// first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchNode {
+    pub position: IVec2,
+    pub cost: u32,
+    pub estimate: u32,
+}
+
+impl PartialOrd for SearchNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SearchNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
@@ -52,7 +52,7 @@ pub trait Rand {
         }
     }
 
-    /// Returns a random value in the range [min, max].
+    /// Returns a random value in the range, `a..b` never returns `b` while `a..=b` may.
     fn range<Value: RandomValue, R: RangeBounds<Value>>(&mut self, range: R) -> Value {
         let start = match range.start_bound() {
             Bound::Included(&start) => start.to_u64(),
@@ -68,7 +68,7 @@ pub trait Rand {
         let min = start.min(end);
         let difference = start.max(end) - min;
 
-        Value::from_u64(self.max_inclusive(difference) + min)
+        Value::from_u64(self.max(difference) + min)
     }
 
     /// Returns a random f64
@@ -121,3 +121,23 @@ impl<T: RngCore> Rand for T {
         self.try_fill_bytes(dest)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn range() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let exclusive = (0..1000)
+            .map(|_| rng.range(0..4u32))
+            .collect::<HashSet<_>>();
+        assert_eq!(exclusive, HashSet::from_iter(0..4));
+
+        let inclusive = (0..1000)
+            .map(|_| rng.range(3..=5u32))
+            .collect::<HashSet<_>>();
+        assert_eq!(inclusive, HashSet::from_iter(3..=5));
+        assert_eq!(rng.range(7..=7u32), 7);
+    }
+}